@@ -0,0 +1,12 @@
+ALTER TABLE nouns
+    ADD COLUMN frequency_rank INT UNSIGNED NULL;
+
+UPDATE nouns
+SET frequency_rank = id;
+
+ALTER TABLE nouns
+    MODIFY COLUMN frequency_rank INT UNSIGNED NOT NULL,
+    ADD INDEX idx_frequency_rank (frequency_rank);
+
+ALTER TABLE users
+    MODIFY COLUMN difficulty ENUM('beginner', 'intermediate', 'advanced', 'expert') NOT NULL DEFAULT 'beginner';
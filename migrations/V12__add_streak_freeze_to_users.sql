@@ -0,0 +1,2 @@
+ALTER TABLE users
+    ADD COLUMN streak_freeze BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,12 @@
+CREATE TABLE sessions
+(
+    id             INT AUTO_INCREMENT PRIMARY KEY,
+    user_id        INT      NOT NULL,
+    first_play_id  INT      NOT NULL,
+    started_at     DATETIME NOT NULL,
+    last_active_at DATETIME NOT NULL,
+    ended_at       DATETIME NULL,
+    INDEX idx_user_ended (user_id, ended_at),
+    FOREIGN KEY (user_id) REFERENCES users (id),
+    FOREIGN KEY (first_play_id) REFERENCES user_plays (id)
+);
@@ -0,0 +1,6 @@
+ALTER TABLE user_plays
+    ADD COLUMN outcome ENUM('correct', 'incorrect', 'unknown', 'skipped') NULL;
+
+UPDATE user_plays
+SET outcome = IF(answer, 'correct', 'incorrect')
+WHERE answer IS NOT NULL;
@@ -0,0 +1,11 @@
+ALTER TABLE nouns
+    CHANGE COLUMN spanish word VARCHAR(255) NOT NULL,
+    ADD COLUMN language ENUM('es', 'fr', 'it', 'pt', 'de') NOT NULL DEFAULT 'es' AFTER word,
+    MODIFY COLUMN gender ENUM('masculine', 'feminine', 'neuter', 'any') NOT NULL;
+
+ALTER TABLE nouns
+    DROP INDEX unique_spanish,
+    ADD UNIQUE KEY unique_language_word (language, word);
+
+ALTER TABLE users
+    ADD COLUMN language ENUM('es', 'fr', 'it', 'pt', 'de') NOT NULL DEFAULT 'es';
@@ -0,0 +1,9 @@
+ALTER TABLE user_plays
+    MODIFY COLUMN timestamp DATETIME(3) NOT NULL DEFAULT CURRENT_TIMESTAMP(3),
+    ADD COLUMN chosen_answer VARCHAR(16) NULL,
+    ADD COLUMN answer_text VARCHAR(255) NULL,
+    ADD COLUMN latency_ms INT UNSIGNED NULL;
+
+UPDATE user_plays
+SET chosen_answer = 'unknown'
+WHERE outcome IS NOT NULL;
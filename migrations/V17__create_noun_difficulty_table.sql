@@ -0,0 +1,10 @@
+CREATE TABLE noun_difficulty
+(
+    noun_id         INT          NOT NULL PRIMARY KEY,
+    answered        INT UNSIGNED NOT NULL,
+    correct         INT UNSIGNED NOT NULL,
+    mean_latency_ms INT UNSIGNED NULL,
+    refreshed_at    DATETIME     NOT NULL,
+    INDEX idx_answered (answered),
+    FOREIGN KEY (noun_id) REFERENCES nouns (id)
+);
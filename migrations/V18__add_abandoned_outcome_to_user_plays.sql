@@ -0,0 +1,2 @@
+ALTER TABLE user_plays
+    MODIFY COLUMN outcome ENUM('correct', 'incorrect', 'unknown', 'skipped', 'abandoned') NULL;
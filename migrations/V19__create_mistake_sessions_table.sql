@@ -0,0 +1,9 @@
+CREATE TABLE mistake_sessions
+(
+    user_id      INT          NOT NULL PRIMARY KEY,
+    queue        JSON         NOT NULL,
+    answered     INT UNSIGNED NOT NULL,
+    correct      INT UNSIGNED NOT NULL,
+    still_missed JSON         NOT NULL,
+    FOREIGN KEY (user_id) REFERENCES users (id)
+);
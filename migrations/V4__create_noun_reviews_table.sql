@@ -0,0 +1,13 @@
+CREATE TABLE noun_reviews
+(
+    user_id          INT      NOT NULL,
+    noun_id          INT      NOT NULL,
+    repetitions      INT      NOT NULL DEFAULT 0,
+    interval_minutes INT      NOT NULL DEFAULT 0,
+    ease_factor      DOUBLE   NOT NULL DEFAULT 2.5,
+    due_at           DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (user_id, noun_id),
+    INDEX idx_user_due (user_id, due_at),
+    FOREIGN KEY (user_id) REFERENCES users (id),
+    FOREIGN KEY (noun_id) REFERENCES nouns (id)
+);
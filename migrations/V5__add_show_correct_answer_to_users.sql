@@ -0,0 +1,2 @@
+ALTER TABLE users
+    ADD COLUMN show_correct_answer BOOLEAN NOT NULL DEFAULT TRUE;
@@ -0,0 +1,27 @@
+ALTER TABLE nouns
+    ADD COLUMN plural     VARCHAR(255) NULL,
+    ADD COLUMN stressed_a BOOLEAN      NOT NULL DEFAULT FALSE;
+
+UPDATE nouns
+SET stressed_a = TRUE
+WHERE gender = 'feminine'
+  AND spanish IN ('agua', 'águila', 'ala', 'alba', 'alga', 'alma', 'ama', 'ancla', 'ansia', 'arca',
+                  'área', 'arma', 'arpa', 'asa', 'asma', 'aspa', 'aula', 'ave', 'haba', 'habla',
+                  'hacha', 'hada', 'hambre', 'hampa');
+
+UPDATE nouns
+SET plural = CASE spanish
+                 WHEN 'carácter' THEN 'caracteres'
+                 WHEN 'crimen' THEN 'crímenes'
+                 WHEN 'desorden' THEN 'desórdenes'
+                 WHEN 'examen' THEN 'exámenes'
+                 WHEN 'imagen' THEN 'imágenes'
+                 WHEN 'bicho raro' THEN 'bichos raros'
+                 WHEN 'fin de semana' THEN 'fines de semana'
+                 WHEN 'hijo de puta' THEN 'hijos de puta'
+                 WHEN 'orden de arresto' THEN 'órdenes de arresto'
+                 WHEN 'planta baja' THEN 'plantas bajas'
+                 WHEN 'ser humano' THEN 'seres humanos'
+    END
+WHERE spanish IN ('carácter', 'crimen', 'desorden', 'examen', 'imagen', 'bicho raro', 'fin de semana',
+                  'hijo de puta', 'orden de arresto', 'planta baja', 'ser humano');
@@ -0,0 +1,2 @@
+ALTER TABLE users
+    ADD COLUMN quiz_mode ENUM('gender', 'article', 'mixed') NOT NULL DEFAULT 'gender';
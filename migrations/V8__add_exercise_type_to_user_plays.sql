@@ -0,0 +1,2 @@
+ALTER TABLE user_plays
+    ADD COLUMN exercise_type ENUM('gender', 'singular_article', 'plural_article') NOT NULL DEFAULT 'gender';
@@ -0,0 +1,6 @@
+ALTER TABLE users
+    ADD COLUMN daily_goal         INT UNSIGNED      NOT NULL DEFAULT 20,
+    ADD COLUMN reminder_time      SMALLINT UNSIGNED NULL COMMENT 'Minutes after local midnight',
+    ADD COLUMN utc_offset_minutes SMALLINT          NOT NULL DEFAULT 0,
+    ADD COLUMN ui_language        VARCHAR(8)        NOT NULL DEFAULT 'en',
+    ADD COLUMN difficulty         ENUM('beginner', 'intermediate', 'advanced') NOT NULL DEFAULT 'beginner';
@@ -0,0 +1,50 @@
+use el_la_la::client::chat::ChatEvent;
+use el_la_la::client::terminal::TerminalClient;
+use el_la_la::domain::{Language, Noun};
+use el_la_la::repository::in_memory::InMemoryDatabase;
+use el_la_la::repository::loader::{self, LoadReport};
+use el_la_la::service::bot::BotService;
+use std::env;
+use std::error::Error;
+use std::sync::mpsc::{channel, Receiver, Sender};
+
+/// Plays the game in the terminal, without a Telegram bot token nor a
+/// database: progress is kept in memory until quitting. Every bundled dataset
+/// is played unless a CSV path is given, so a new noun list can be play-tested
+/// right away. It holds Spanish nouns unless a language code follows the path.
+/// No reminders are scheduled, so the game ends once stdin does.
+fn main() -> Result<(), Box<dyn Error>> {
+    let play_tested: Option<String> = env::args().nth(1);
+    let datasets: Vec<(String, Language)> = match &play_tested {
+        Some(path) => {
+            let language: Language = match env::args().nth(2) {
+                Some(language_str) => language_str.parse()?,
+                None => Language::Spanish,
+            };
+            vec![(path.clone(), language)]
+        }
+        None => Language::ALL
+            .iter()
+            .map(|language| (loader::default_path(*language), *language))
+            .collect(),
+    };
+    let mut nouns: Vec<Noun> = Vec::new();
+    for (path, language) in datasets {
+        let first_id: i32 = nouns.len() as i32 + 1;
+        let (dataset, report): (Vec<Noun>, LoadReport) =
+            loader::read_nouns(&path, language, first_id)?;
+        // Rejected rows only matter when play-testing a new list.
+        if play_tested.is_some() {
+            println!("{}", report);
+        } else {
+            println!("{}: {} nouns", language, dataset.len());
+        }
+        nouns.extend(dataset);
+    }
+    println!("Type /start to play, a choice number to answer and Ctrl+D to quit.\n");
+
+    let database: InMemoryDatabase = InMemoryDatabase::with_nouns(nouns);
+    let (channel_sender, channel_receiver): (Sender<ChatEvent>, Receiver<ChatEvent>) = channel();
+    let terminal_client: TerminalClient = TerminalClient::new(channel_sender);
+    BotService::new(terminal_client, database.repositories()).run(channel_receiver)
+}
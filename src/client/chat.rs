@@ -0,0 +1,91 @@
+use std::error::Error;
+use std::time::SystemTime;
+
+/// Incoming event from a chat platform, already stripped of the
+/// platform-specific types, or a tick from the scheduler.
+#[derive(Debug, Clone)]
+pub enum ChatEvent {
+    Message(IncomingMessage),
+    Choice(IncomingChoice),
+    /// Periodic wake-up for the work not triggered by users, such as sending
+    /// reminders, carrying the time it fired at.
+    Tick(SystemTime),
+}
+
+/// Text sent by a user.
+#[derive(Debug, Clone)]
+pub struct IncomingMessage {
+    pub chat_id: i64,
+    pub user_id: u64,
+    /// Language the user's app is set to, as an IETF tag such as `pt-BR`.
+    pub language_code: Option<String>,
+    pub message_id: i32,
+    pub text: String,
+}
+
+/// Choice picked by a user on a question previously sent with
+/// [`ChatClient::send_question`].
+#[derive(Debug, Clone)]
+pub struct IncomingChoice {
+    pub id: String,
+    pub chat_id: i64,
+    pub user_id: u64,
+    pub language_code: Option<String>,
+    pub message_id: i32,
+    pub question_text: Option<String>,
+    pub data: String,
+}
+
+/// Answer offered on a question: `label` is shown to the user and `data` is
+/// sent back in the resulting [`IncomingChoice`].
+#[derive(Debug, Clone)]
+pub struct Choice {
+    pub label: String,
+    pub data: String,
+}
+
+impl Choice {
+    pub fn new(label: &str, data: String) -> Self {
+        Self {
+            label: label.to_string(),
+            data,
+        }
+    }
+}
+
+pub trait ChatClient {
+    fn send_message(&self, chat_id: i64, text: &str) -> Result<(), Box<dyn Error>>;
+    fn send_reply(
+        &self,
+        chat_id: i64,
+        reply_to_message_id: i32,
+        text: &str,
+    ) -> Result<(), Box<dyn Error>>;
+    fn send_question(
+        &self,
+        chat_id: i64,
+        text: &str,
+        choices: &[Choice],
+    ) -> Result<(), Box<dyn Error>>;
+    /// Sends a message with choices laid out in rows.
+    fn send_menu(
+        &self,
+        chat_id: i64,
+        text: &str,
+        rows: &[Vec<Choice>],
+    ) -> Result<(), Box<dyn Error>>;
+    /// Replaces the text of a message, dropping any choices attached to it.
+    fn edit_message(&self, chat_id: i64, message_id: i32, text: &str)
+        -> Result<(), Box<dyn Error>>;
+    /// Replaces both the text and the choices of a message.
+    fn edit_menu(
+        &self,
+        chat_id: i64,
+        message_id: i32,
+        text: &str,
+        rows: &[Vec<Choice>],
+    ) -> Result<(), Box<dyn Error>>;
+    fn react(&self, chat_id: i64, message_id: i32, reaction: &str) -> Result<(), Box<dyn Error>>;
+    fn acknowledge_choice(&self, choice_id: &str, text: Option<&str>)
+        -> Result<(), Box<dyn Error>>;
+}
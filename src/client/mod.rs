@@ -1 +1,4 @@
+pub mod chat;
 pub mod telegram;
+pub mod terminal;
+pub mod webhook;
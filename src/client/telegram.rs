@@ -1,7 +1,13 @@
+use crate::client::chat::{ChatClient, ChatEvent, Choice, IncomingChoice, IncomingMessage};
+use crate::client::webhook::{WebhookConfig, WebhookServer};
+use crate::domain::UiLanguage;
+use crate::i18n;
 use frankenstein::{
-    Api, BotCommand, GetUpdatesParams, KeyboardButton, Message, MethodResponse, ReactionType,
-    ReactionTypeEmoji, ReplyKeyboardMarkup, ReplyMarkup, SendMessageParams,
-    SetMessageReactionParams, SetMyCommandsParams, TelegramApi, Update,
+    AnswerCallbackQueryParams, Api, BotCommand, CallbackQuery, DeleteWebhookParams,
+    EditMessageTextParams, GetUpdatesParams, InlineKeyboardButton, InlineKeyboardMarkup,
+    MaybeInaccessibleMessage, ReactionType, ReactionTypeEmoji, ReplyMarkup, ReplyParameters,
+    SendMessageParams, SetMessageReactionParams, SetMyCommandsParams, SetWebhookParams,
+    TelegramApi, Update, UpdateContent,
 };
 use std::error::Error;
 use std::sync::mpsc::Sender;
@@ -9,124 +15,290 @@ use std::{env, thread};
 
 pub struct TelegramClient {
     api: Api,
-    question_keyboard: ReplyMarkup,
 }
 
 impl TelegramClient {
-    pub fn new(channel_sender: Sender<Update>) -> Self {
+    pub fn new(channel_sender: Sender<ChatEvent>) -> Self {
         let token: String = env::var("TELEGRAM_BOT_TOKEN").expect("Bot token not found");
-        let question_keyboard: ReplyMarkup = Self::build_keyboard();
+
+        let webhook_config: Option<WebhookConfig> =
+            WebhookConfig::from_env().expect("Invalid webhook configuration");
 
         let api: Api = Api::new(token.as_str());
-        let api_clone: Api = api.clone();
         Self::set_bot_commands(&api).expect("Failed to set bot commands");
+        match webhook_config {
+            Some(config) => Self::listen_webhook(&api, &config, channel_sender)
+                .expect("Failed to set up the webhook"),
+            None => Self::start_polling(&api, channel_sender).expect("Failed to start polling"),
+        }
+
+        Self { api }
+    }
+
+    /// Translates a Telegram update into a platform-neutral event. Updates the
+    /// bot doesn't react to are dropped.
+    pub fn to_chat_event(update: Update) -> Option<ChatEvent> {
+        match update.content {
+            UpdateContent::Message(message) => {
+                let from = message.from?;
+                Some(ChatEvent::Message(IncomingMessage {
+                    chat_id: message.chat.id,
+                    user_id: from.id,
+                    language_code: from.language_code,
+                    message_id: message.message_id,
+                    text: message.text?,
+                }))
+            }
+            UpdateContent::CallbackQuery(callback_query) => {
+                Self::to_incoming_choice(callback_query).map(ChatEvent::Choice)
+            }
+            _ => None,
+        }
+    }
+
+    fn to_incoming_choice(callback_query: CallbackQuery) -> Option<IncomingChoice> {
+        let (chat_id, message_id, question_text) = match callback_query.message? {
+            MaybeInaccessibleMessage::Message(message) => {
+                (message.chat.id, message.message_id, message.text)
+            }
+            MaybeInaccessibleMessage::InaccessibleMessage(message) => {
+                (message.chat.id, message.message_id, None)
+            }
+        };
+        Some(IncomingChoice {
+            id: callback_query.id,
+            chat_id,
+            user_id: callback_query.from.id,
+            language_code: callback_query.from.language_code,
+            message_id,
+            question_text,
+            data: callback_query.data?,
+        })
+    }
+
+    /// Registers the commands with their descriptions in each interface
+    /// language, English being the default for the languages without one.
+    fn set_bot_commands(api: &Api) -> Result<(), Box<dyn Error>> {
+        for ui_language in UiLanguage::ALL {
+            let language_code: Option<String> = match ui_language {
+                UiLanguage::English => None,
+                _ => Some(ui_language.as_str().to_string()),
+            };
+            let commands: Vec<BotCommand> = i18n::COMMANDS
+                .iter()
+                .map(|command| {
+                    let key: String = format!("command-{}", command);
+                    BotCommand::builder()
+                        .command(*command)
+                        .description(i18n::text(ui_language, &key))
+                        .build()
+                })
+                .collect();
+            let params = SetMyCommandsParams {
+                commands,
+                scope: None,
+                language_code,
+            };
+            api.set_my_commands(&params)?;
+        }
+        Ok(())
+    }
+
+    fn listen_webhook(
+        api: &Api,
+        config: &WebhookConfig,
+        channel_sender: Sender<ChatEvent>,
+    ) -> Result<(), Box<dyn Error>> {
+        let server: WebhookServer = WebhookServer::bind(config)?;
+        let set_webhook_params = SetWebhookParams::builder()
+            .url(config.webhook_url())
+            .secret_token(config.secret_token.clone())
+            .build();
+        api.set_webhook(&set_webhook_params)?;
+        println!("Receiving updates on webhook {}", config.webhook_url());
+        thread::spawn(move || server.run(channel_sender));
+        Ok(())
+    }
+
+    /// Telegram refuses `getUpdates` while a webhook is registered, so any
+    /// webhook left over from a previous run is removed first.
+    fn start_polling(api: &Api, channel_sender: Sender<ChatEvent>) -> Result<(), Box<dyn Error>> {
+        let delete_webhook_params = DeleteWebhookParams::builder().build();
+        api.delete_webhook(&delete_webhook_params)?;
+        let api_clone: Api = api.clone();
         thread::spawn(move || Self::poll_updates(api_clone, channel_sender));
+        Ok(())
+    }
+
+    fn poll_updates(api: Api, event_sender: Sender<ChatEvent>) {
+        let mut update_params: GetUpdatesParams = GetUpdatesParams::builder().timeout(10).build();
 
-        Self {
-            api,
-            question_keyboard,
+        loop {
+            match api.get_updates(&update_params) {
+                Ok(response) => {
+                    for update in response.result {
+                        update_params.offset = Some(i64::from(update.update_id) + 1);
+                        let Some(event) = Self::to_chat_event(update) else {
+                            continue;
+                        };
+                        if let Err(err) = event_sender.send(event) {
+                            eprintln!("Failed to send update: {}", err);
+                            return;
+                        }
+                    }
+                }
+                Err(error) => {
+                    eprintln!("Error getting updates: {:?}", error);
+                    thread::sleep(std::time::Duration::from_secs(5));
+                }
+            }
         }
     }
 
-    pub fn send_reaction(
+    fn build_keyboard(rows: &[Vec<Choice>]) -> InlineKeyboardMarkup {
+        let keyboard: Vec<Vec<InlineKeyboardButton>> = rows
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|choice| {
+                        InlineKeyboardButton::builder()
+                            .text(choice.label.as_str())
+                            .callback_data(choice.data.as_str())
+                            .build()
+                    })
+                    .collect()
+            })
+            .collect();
+        InlineKeyboardMarkup::builder()
+            .inline_keyboard(keyboard)
+            .build()
+    }
+}
+
+impl ChatClient for TelegramClient {
+    fn send_message(&self, chat_id: i64, text: &str) -> Result<(), Box<dyn Error>> {
+        let send_message_params = SendMessageParams::builder()
+            .chat_id(chat_id)
+            .text(text)
+            .build();
+
+        self.api
+            .send_message(&send_message_params)
+            .map(|_| ())
+            .map_err(|e| e.into())
+    }
+
+    fn send_reply(
         &self,
         chat_id: i64,
-        message_id: i32,
-        reaction: &str,
+        reply_to_message_id: i32,
+        text: &str,
     ) -> Result<(), Box<dyn Error>> {
-        let set_message_reaction_params = SetMessageReactionParams::builder()
+        let reply_parameters = ReplyParameters::builder()
+            .message_id(reply_to_message_id)
+            .build();
+        let send_message_params = SendMessageParams::builder()
             .chat_id(chat_id)
-            .message_id(message_id)
-            .reaction(vec![ReactionType::Emoji(ReactionTypeEmoji {
-                emoji: reaction.to_string(),
-            })])
+            .text(text)
+            .reply_parameters(reply_parameters)
             .build();
-        self.api.set_message_reaction(&set_message_reaction_params)
+
+        self.api
+            .send_message(&send_message_params)
             .map(|_| ())
             .map_err(|e| e.into())
     }
 
-    pub fn send_message(
+    fn send_question(
+        &self,
+        chat_id: i64,
+        text: &str,
+        choices: &[Choice],
+    ) -> Result<(), Box<dyn Error>> {
+        self.send_menu(chat_id, text, &[choices.to_vec()])
+    }
+
+    fn send_menu(
         &self,
         chat_id: i64,
         text: &str,
-    ) -> Result<MethodResponse<Message>, frankenstein::Error> {
+        rows: &[Vec<Choice>],
+    ) -> Result<(), Box<dyn Error>> {
+        let keyboard_markup: InlineKeyboardMarkup = Self::build_keyboard(rows);
         let send_message_params = SendMessageParams::builder()
             .chat_id(chat_id)
             .text(text)
+            .reply_markup(ReplyMarkup::InlineKeyboardMarkup(keyboard_markup))
             .build();
 
-        self.api.send_message(&send_message_params)
+        self.api
+            .send_message(&send_message_params)
+            .map(|_| ())
+            .map_err(|e| e.into())
     }
 
-    pub fn send_question(
+    fn edit_message(
         &self,
         chat_id: i64,
+        message_id: i32,
         text: &str,
-    ) -> Result<MethodResponse<Message>, frankenstein::Error> {
-        let send_message_params = SendMessageParams::builder()
+    ) -> Result<(), Box<dyn Error>> {
+        let edit_message_text_params = EditMessageTextParams::builder()
             .chat_id(chat_id)
+            .message_id(message_id)
             .text(text)
-            .reply_markup(self.question_keyboard.clone())
             .build();
-
-        self.api.send_message(&send_message_params)
+        self.api
+            .edit_message_text(&edit_message_text_params)
+            .map(|_| ())
+            .map_err(|e| e.into())
     }
 
-    fn set_bot_commands(api: &Api) -> Result<(), Box<dyn Error>> {
-        let commands = vec![
-            BotCommand::builder()
-                .command("start")
-                .description("Start the game")
-                .build(),
-            BotCommand::builder()
-                .command("stop")
-                .description("Stop the game")
-                .build(),
-            BotCommand::builder()
-                .command("help")
-                .description("Show help information")
-                .build(),
-            BotCommand::builder()
-                .command("stats")
-                .description("Show your statistics")
-                .build(),
-        ];
-
-        let params = SetMyCommandsParams::builder().commands(commands).build();
-        api.set_my_commands(&params).map(|_| ()).map_err(|e| e.into())
-    }
-
-    fn poll_updates(api: Api, update_sender: Sender<Update>) {
-        let mut update_params: GetUpdatesParams = GetUpdatesParams::builder().timeout(10).build();
+    fn edit_menu(
+        &self,
+        chat_id: i64,
+        message_id: i32,
+        text: &str,
+        rows: &[Vec<Choice>],
+    ) -> Result<(), Box<dyn Error>> {
+        let edit_message_text_params = EditMessageTextParams::builder()
+            .chat_id(chat_id)
+            .message_id(message_id)
+            .text(text)
+            .reply_markup(Self::build_keyboard(rows))
+            .build();
+        self.api
+            .edit_message_text(&edit_message_text_params)
+            .map(|_| ())
+            .map_err(|e| e.into())
+    }
 
-        loop {
-            match api.get_updates(&update_params) {
-                Ok(response) => {
-                    for update in response.result {
-                        if let Err(err) = update_sender.send(update.clone()) {
-                            eprintln!("Failed to send update: {}", err);
-                            return;
-                        }
-                        update_params.offset = Some(i64::from(update.update_id) + 1);
-                    }
-                }
-                Err(error) => {
-                    eprintln!("Error getting updates: {:?}", error);
-                    thread::sleep(std::time::Duration::from_secs(5));
-                }
-            }
-        }
+    fn react(&self, chat_id: i64, message_id: i32, reaction: &str) -> Result<(), Box<dyn Error>> {
+        let set_message_reaction_params = SetMessageReactionParams::builder()
+            .chat_id(chat_id)
+            .message_id(message_id)
+            .reaction(vec![ReactionType::Emoji(ReactionTypeEmoji {
+                emoji: reaction.to_string(),
+            })])
+            .build();
+        self.api
+            .set_message_reaction(&set_message_reaction_params)
+            .map(|_| ())
+            .map_err(|e| e.into())
     }
 
-    fn build_keyboard() -> ReplyMarkup {
-        let row: Vec<KeyboardButton> = vec![
-            KeyboardButton::builder().text("Masculine").build(),
-            KeyboardButton::builder().text("Feminine").build(),
-            KeyboardButton::builder().text("Any").build(),
-        ];
-        let keyboard_markup: ReplyKeyboardMarkup =
-            ReplyKeyboardMarkup::builder().keyboard(vec![row]).build();
-        ReplyMarkup::ReplyKeyboardMarkup(keyboard_markup)
+    fn acknowledge_choice(
+        &self,
+        choice_id: &str,
+        text: Option<&str>,
+    ) -> Result<(), Box<dyn Error>> {
+        let answer_callback_query_params = AnswerCallbackQueryParams::builder()
+            .callback_query_id(choice_id)
+            .maybe_text(text)
+            .build();
+        self.api
+            .answer_callback_query(&answer_callback_query_params)
+            .map(|_| ())
+            .map_err(|e| e.into())
     }
 }
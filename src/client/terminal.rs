@@ -0,0 +1,208 @@
+use crate::client::chat::{ChatClient, ChatEvent, Choice, IncomingChoice, IncomingMessage};
+use std::error::Error;
+use std::io::{stdin, BufRead};
+use std::sync::atomic::{AtomicI32, Ordering};
+use std::sync::mpsc::Sender;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+pub const TERMINAL_CHAT_ID: i64 = 0;
+pub const TERMINAL_USER_ID: u64 = 0;
+
+#[derive(Debug, Clone)]
+struct PendingQuestion {
+    message_id: i32,
+    text: String,
+    choices: Vec<Choice>,
+}
+
+/// Interactive frontend playing the bot on stdin/stdout. Questions list their
+/// choices with a number, and typing that number picks the choice.
+pub struct TerminalClient {
+    pending_question: Arc<Mutex<Option<PendingQuestion>>>,
+    message_ids: Arc<AtomicI32>,
+}
+
+impl TerminalClient {
+    pub fn new(event_sender: Sender<ChatEvent>) -> Self {
+        let pending_question: Arc<Mutex<Option<PendingQuestion>>> = Arc::new(Mutex::new(None));
+        let message_ids: Arc<AtomicI32> = Arc::new(AtomicI32::new(0));
+
+        let pending_question_clone = Arc::clone(&pending_question);
+        let message_ids_clone = Arc::clone(&message_ids);
+        thread::spawn(move || {
+            Self::read_input(pending_question_clone, message_ids_clone, event_sender)
+        });
+
+        Self {
+            pending_question,
+            message_ids,
+        }
+    }
+
+    fn read_input(
+        pending_question: Arc<Mutex<Option<PendingQuestion>>>,
+        message_ids: Arc<AtomicI32>,
+        event_sender: Sender<ChatEvent>,
+    ) {
+        for line in stdin().lock().lines() {
+            let text: String = match line {
+                Ok(line) => line.trim().to_string(),
+                Err(err) => {
+                    eprintln!("Failed to read input: {}", err);
+                    return;
+                }
+            };
+            if text.is_empty() {
+                continue;
+            }
+            let message_id: i32 = message_ids.fetch_add(1, Ordering::SeqCst) + 1;
+            let event: ChatEvent = match Self::pick_choice(&pending_question, &text) {
+                Some((question, choice)) => ChatEvent::Choice(IncomingChoice {
+                    id: message_id.to_string(),
+                    chat_id: TERMINAL_CHAT_ID,
+                    user_id: TERMINAL_USER_ID,
+                    language_code: None,
+                    message_id: question.message_id,
+                    question_text: Some(question.text),
+                    data: choice.data,
+                }),
+                None => ChatEvent::Message(IncomingMessage {
+                    chat_id: TERMINAL_CHAT_ID,
+                    user_id: TERMINAL_USER_ID,
+                    language_code: None,
+                    message_id,
+                    text,
+                }),
+            };
+            if let Err(err) = event_sender.send(event) {
+                eprintln!("Failed to send input: {}", err);
+                return;
+            }
+        }
+    }
+
+    fn pick_choice(
+        pending_question: &Mutex<Option<PendingQuestion>>,
+        text: &str,
+    ) -> Option<(PendingQuestion, Choice)> {
+        let index: usize = text.parse::<usize>().ok()?.checked_sub(1)?;
+        let mut pending_question = pending_question.lock().ok()?;
+        let choice: Choice = pending_question.as_ref()?.choices.get(index)?.clone();
+        pending_question.take().map(|question| (question, choice))
+    }
+
+    fn next_message_id(&self) -> i32 {
+        self.message_ids.fetch_add(1, Ordering::SeqCst) + 1
+    }
+
+    /// Prints the choices numbered across rows, one row per line.
+    fn show_menu(
+        &self,
+        message_id: i32,
+        text: &str,
+        rows: &[Vec<Choice>],
+    ) -> Result<(), Box<dyn Error>> {
+        let mut number: usize = 0;
+        let lines: Vec<String> = rows
+            .iter()
+            .map(|row| {
+                let options: Vec<String> = row
+                    .iter()
+                    .map(|choice| {
+                        number += 1;
+                        format!("[{}] {}", number, choice.label)
+                    })
+                    .collect();
+                options.join("  ")
+            })
+            .collect();
+        println!("{}\n{}\n", text, lines.join("\n"));
+
+        let question = PendingQuestion {
+            message_id,
+            text: text.to_string(),
+            choices: rows.concat(),
+        };
+        *self
+            .pending_question
+            .lock()
+            .map_err(|_| "Pending question lock poisoned")? = Some(question);
+        Ok(())
+    }
+}
+
+impl ChatClient for TerminalClient {
+    fn send_message(&self, _chat_id: i64, text: &str) -> Result<(), Box<dyn Error>> {
+        self.next_message_id();
+        println!("{}\n", text);
+        Ok(())
+    }
+
+    fn send_reply(
+        &self,
+        _chat_id: i64,
+        _reply_to_message_id: i32,
+        text: &str,
+    ) -> Result<(), Box<dyn Error>> {
+        self.next_message_id();
+        println!("↪ {}\n", text);
+        Ok(())
+    }
+
+    fn send_question(
+        &self,
+        chat_id: i64,
+        text: &str,
+        choices: &[Choice],
+    ) -> Result<(), Box<dyn Error>> {
+        self.send_menu(chat_id, text, &[choices.to_vec()])
+    }
+
+    fn send_menu(
+        &self,
+        _chat_id: i64,
+        text: &str,
+        rows: &[Vec<Choice>],
+    ) -> Result<(), Box<dyn Error>> {
+        let message_id: i32 = self.next_message_id();
+        self.show_menu(message_id, text, rows)
+    }
+
+    fn edit_message(
+        &self,
+        _chat_id: i64,
+        _message_id: i32,
+        text: &str,
+    ) -> Result<(), Box<dyn Error>> {
+        let last_line: &str = text.lines().last().unwrap_or_default();
+        println!("{}\n", last_line);
+        Ok(())
+    }
+
+    fn edit_menu(
+        &self,
+        _chat_id: i64,
+        message_id: i32,
+        text: &str,
+        rows: &[Vec<Choice>],
+    ) -> Result<(), Box<dyn Error>> {
+        self.show_menu(message_id, text, rows)
+    }
+
+    fn react(&self, _chat_id: i64, _message_id: i32, reaction: &str) -> Result<(), Box<dyn Error>> {
+        println!("{}\n", reaction);
+        Ok(())
+    }
+
+    fn acknowledge_choice(
+        &self,
+        _choice_id: &str,
+        text: Option<&str>,
+    ) -> Result<(), Box<dyn Error>> {
+        if let Some(text) = text {
+            println!("{}\n", text);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,130 @@
+use crate::client::chat::ChatEvent;
+use crate::client::telegram::TelegramClient;
+use frankenstein::Update;
+use std::env;
+use std::error::Error;
+use std::net::SocketAddr;
+use std::sync::mpsc::Sender;
+use tiny_http::{Method, Request, Response, Server};
+
+const SECRET_TOKEN_HEADER: &str = "X-Telegram-Bot-Api-Secret-Token";
+
+pub struct WebhookConfig {
+    pub public_url: String,
+    pub path: String,
+    pub listen_address: String,
+    pub secret_token: String,
+}
+
+impl WebhookConfig {
+    /// Webhook mode is enabled by `TELEGRAM_WEBHOOK_URL`, the public base URL
+    /// Telegram posts updates to. Without it the bot falls back to polling.
+    pub fn from_env() -> Result<Option<Self>, Box<dyn Error>> {
+        let public_url: String = match env::var("TELEGRAM_WEBHOOK_URL") {
+            Ok(url) => url.trim_end_matches('/').to_string(),
+            Err(_) => return Ok(None),
+        };
+        let secret_token: String = env::var("TELEGRAM_WEBHOOK_SECRET")
+            .map_err(|_| "TELEGRAM_WEBHOOK_SECRET is required when TELEGRAM_WEBHOOK_URL is set")?;
+        let path: String = env::var("TELEGRAM_WEBHOOK_PATH").unwrap_or("/telegram/webhook".into());
+        let listen_address: String =
+            env::var("TELEGRAM_WEBHOOK_LISTEN").unwrap_or("0.0.0.0:8080".into());
+
+        Ok(Some(Self {
+            public_url,
+            path,
+            listen_address,
+            secret_token,
+        }))
+    }
+
+    pub fn webhook_url(&self) -> String {
+        format!("{}{}", self.public_url, self.path)
+    }
+}
+
+/// Embedded HTTP server receiving the updates Telegram pushes to the webhook
+/// and forwarding them as events to the same channel the long polling feeds.
+pub struct WebhookServer {
+    server: Server,
+    path: String,
+    secret_token: String,
+}
+
+impl WebhookServer {
+    pub fn bind(config: &WebhookConfig) -> Result<Self, Box<dyn Error>> {
+        let server: Server = Server::http(config.listen_address.as_str())
+            .map_err(|e| format!("Cannot listen on {}: {}", config.listen_address, e))?;
+        Ok(Self {
+            server,
+            path: config.path.clone(),
+            secret_token: config.secret_token.clone(),
+        })
+    }
+
+    /// Address the server listens on, which tells the port picked by the
+    /// system when binding to port 0.
+    pub fn local_address(&self) -> Option<SocketAddr> {
+        self.server.server_addr().to_ip()
+    }
+
+    pub fn run(self, event_sender: Sender<ChatEvent>) {
+        for mut request in self.server.incoming_requests() {
+            let (status_code, keep_running) = match self.read_update(&mut request) {
+                Ok(update) => match TelegramClient::to_chat_event(update) {
+                    Some(event) => match event_sender.send(event) {
+                        Ok(()) => (200, true),
+                        Err(err) => {
+                            eprintln!("Failed to send update: {}", err);
+                            (503, false)
+                        }
+                    },
+                    None => (200, true),
+                },
+                Err(status_code) => (status_code, true),
+            };
+            request
+                .respond(Response::empty(status_code))
+                .inspect_err(|e| eprintln!("Failed to answer webhook request: {}", e))
+                .ok();
+            if !keep_running {
+                return;
+            }
+        }
+    }
+
+    fn read_update(&self, request: &mut Request) -> Result<Update, u16> {
+        if request.url() != self.path {
+            return Err(404);
+        }
+        if *request.method() != Method::Post {
+            return Err(405);
+        }
+        let secret_token: Option<&str> = request
+            .headers()
+            .iter()
+            .find(|header| header.field.equiv(SECRET_TOKEN_HEADER))
+            .map(|header| header.value.as_str());
+        let is_authorized: bool = secret_token
+            .is_some_and(|token| constant_time_eq(token.as_bytes(), self.secret_token.as_bytes()));
+        if !is_authorized {
+            return Err(401);
+        }
+
+        let mut body: String = String::new();
+        request.as_reader().read_to_string(&mut body).map_err(|e| {
+            eprintln!("Failed to read webhook request: {}", e);
+            400_u16
+        })?;
+        serde_json::from_str(&body).map_err(|e| {
+            eprintln!("Invalid update received on webhook: {}", e);
+            400
+        })
+    }
+}
+
+/// Compares every byte even after a difference, so the response time doesn't
+/// tell how much of a guessed secret token was right. Only the length leaks.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |diff, (x, y)| diff | (x ^ y)) == 0
+}
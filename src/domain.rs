@@ -1,33 +1,819 @@
-#[derive(Debug, Clone)]
+use std::collections::VecDeque;
+use std::fmt;
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Gender {
     Masculine,
     Feminine,
+    Neuter,
     Any,
 }
 
+impl FromStr for Gender {
+    type Err = String;
+
+    fn from_str(gender_str: &str) -> Result<Self, Self::Err> {
+        match gender_str {
+            "masculine" => Ok(Gender::Masculine),
+            "feminine" => Ok(Gender::Feminine),
+            "neuter" => Ok(Gender::Neuter),
+            "any" => Ok(Gender::Any),
+            _ => Err(format!("Invalid gender '{}'", gender_str)),
+        }
+    }
+}
+
+impl fmt::Display for Gender {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label: &str = match self {
+            Gender::Masculine => "Masculine",
+            Gender::Feminine => "Feminine",
+            Gender::Neuter => "Neuter",
+            Gender::Any => "Any",
+        };
+        write!(f, "{}", label)
+    }
+}
+
 impl Gender {
-    pub fn is_match(&self, gender_str: &str) -> bool {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Gender::Masculine => "masculine",
+            Gender::Feminine => "feminine",
+            Gender::Neuter => "neuter",
+            Gender::Any => "any",
+        }
+    }
+}
+
+/// Language a noun belongs to, and the one a user is learning.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Language {
+    Spanish,
+    French,
+    Italian,
+    Portuguese,
+    German,
+}
+
+impl Language {
+    pub const ALL: [Language; 5] = [
+        Language::Spanish,
+        Language::French,
+        Language::Italian,
+        Language::Portuguese,
+        Language::German,
+    ];
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            Language::Spanish => "es",
+            Language::French => "fr",
+            Language::Italian => "it",
+            Language::Portuguese => "pt",
+            Language::German => "de",
+        }
+    }
+
+    pub fn flag(&self) -> &str {
+        match self {
+            Language::Spanish => "🇪🇸",
+            Language::French => "🇫🇷",
+            Language::Italian => "🇮🇹",
+            Language::Portuguese => "🇵🇹",
+            Language::German => "🇩🇪",
+        }
+    }
+
+    /// Genders offered as answers. German nouns taking two articles are too
+    /// rare to offer "any".
+    pub fn answer_genders(&self) -> &'static [Gender] {
+        match self {
+            Language::German => &[Gender::Masculine, Gender::Feminine, Gender::Neuter],
+            _ => &[Gender::Masculine, Gender::Feminine, Gender::Any],
+        }
+    }
+
+    /// Singular definite article for the gender, before any elision.
+    pub fn definite_article(&self, gender: Gender) -> &'static str {
+        match (self, gender) {
+            (Language::Spanish, Gender::Feminine) => "la",
+            (Language::Spanish, Gender::Any) => "el/la",
+            (Language::Spanish, _) => "el",
+            (Language::French, Gender::Feminine) => "la",
+            (Language::French, Gender::Any) => "le/la",
+            (Language::French, _) => "le",
+            (Language::Italian, Gender::Feminine) => "la",
+            (Language::Italian, Gender::Any) => "il/la",
+            (Language::Italian, _) => "il",
+            (Language::Portuguese, Gender::Feminine) => "a",
+            (Language::Portuguese, Gender::Any) => "o/a",
+            (Language::Portuguese, _) => "o",
+            (Language::German, Gender::Masculine) => "der",
+            (Language::German, Gender::Feminine) => "die",
+            (Language::German, Gender::Neuter) => "das",
+            (Language::German, Gender::Any) => "der/die",
+        }
+    }
+
+    /// Singular articles naming a gender when typed as an answer.
+    pub fn gender_articles(&self) -> &'static [(&'static str, Gender)] {
+        match self {
+            Language::Spanish => &[
+                ("el", Gender::Masculine),
+                ("un", Gender::Masculine),
+                ("la", Gender::Feminine),
+                ("una", Gender::Feminine),
+            ],
+            Language::French => &[
+                ("le", Gender::Masculine),
+                ("un", Gender::Masculine),
+                ("la", Gender::Feminine),
+                ("une", Gender::Feminine),
+            ],
+            Language::Italian => &[
+                ("il", Gender::Masculine),
+                ("lo", Gender::Masculine),
+                ("un", Gender::Masculine),
+                ("uno", Gender::Masculine),
+                ("la", Gender::Feminine),
+                ("una", Gender::Feminine),
+            ],
+            Language::Portuguese => &[
+                ("o", Gender::Masculine),
+                ("um", Gender::Masculine),
+                ("a", Gender::Feminine),
+                ("uma", Gender::Feminine),
+            ],
+            Language::German => &[
+                ("der", Gender::Masculine),
+                ("ein", Gender::Masculine),
+                ("die", Gender::Feminine),
+                ("eine", Gender::Feminine),
+                ("das", Gender::Neuter),
+            ],
+        }
+    }
+}
+
+impl FromStr for Language {
+    type Err = String;
+
+    fn from_str(language_str: &str) -> Result<Self, Self::Err> {
+        match language_str {
+            "es" => Ok(Language::Spanish),
+            "fr" => Ok(Language::French),
+            "it" => Ok(Language::Italian),
+            "pt" => Ok(Language::Portuguese),
+            "de" => Ok(Language::German),
+            _ => Err(format!("Invalid language '{}'", language_str)),
+        }
+    }
+}
+
+impl fmt::Display for Language {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label: &str = match self {
+            Language::Spanish => "Spanish",
+            Language::French => "French",
+            Language::Italian => "Italian",
+            Language::Portuguese => "Portuguese",
+            Language::German => "German",
+        };
+        write!(f, "{}", label)
+    }
+}
+
+/// Definite and indefinite articles, singular and plural.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Article {
+    El,
+    La,
+    Los,
+    Las,
+    Un,
+    Una,
+    Unos,
+    Unas,
+}
+
+impl Article {
+    pub const DEFINITE: [Article; 4] = [Article::El, Article::La, Article::Los, Article::Las];
+    pub const INDEFINITE: [Article; 4] = [Article::Un, Article::Una, Article::Unos, Article::Unas];
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            Article::El => "el",
+            Article::La => "la",
+            Article::Los => "los",
+            Article::Las => "las",
+            Article::Un => "un",
+            Article::Una => "una",
+            Article::Unos => "unos",
+            Article::Unas => "unas",
+        }
+    }
+
+    pub fn is_plural(&self) -> bool {
         matches!(
-            (self, gender_str.to_lowercase().as_str()),
-            (Gender::Masculine, "masculine")
-                | (Gender::Feminine, "feminine")
-                | (Gender::Any, "any")
+            self,
+            Article::Los | Article::Las | Article::Unos | Article::Unas
+        )
+    }
+
+    pub fn is_masculine(&self) -> bool {
+        matches!(
+            self,
+            Article::El | Article::Los | Article::Un | Article::Unos
         )
     }
 }
 
+impl FromStr for Article {
+    type Err = String;
+
+    fn from_str(article_str: &str) -> Result<Self, Self::Err> {
+        match article_str {
+            "el" => Ok(Article::El),
+            "la" => Ok(Article::La),
+            "los" => Ok(Article::Los),
+            "las" => Ok(Article::Las),
+            "un" => Ok(Article::Un),
+            "una" => Ok(Article::Una),
+            "unos" => Ok(Article::Unos),
+            "unas" => Ok(Article::Unas),
+            _ => Err(format!("Invalid article '{}'", article_str)),
+        }
+    }
+}
+
+impl fmt::Display for Article {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Noun {
     pub id: i32,
     pub english: String,
-    pub spanish: String,
+    /// The noun in its own language, without article.
+    pub word: String,
+    pub language: Language,
     pub gender: Gender,
+    /// Position in the dataset, which lists the most frequent nouns first.
+    pub frequency_rank: u32,
+    /// Plural form when the regular rules don't produce it.
+    pub plural: Option<String>,
+    /// Feminine nouns starting with a stressed "a" take "el" and "un" in the
+    /// singular: "el agua", "las aguas".
+    pub stressed_a: bool,
+}
+
+impl Noun {
+    pub fn with_article(&self) -> String {
+        self.with_article_for(self.gender)
+    }
+
+    /// The noun with the singular article of the given gender, which differs
+    /// from its own for nouns taking either article.
+    pub fn with_article_for(&self, gender: Gender) -> String {
+        let article: &str = self.singular_article(gender);
+        if article.ends_with('\'') {
+            format!("{}{}", article, self.word)
+        } else {
+            format!("{} {}", article, self.word)
+        }
+    }
+
+    /// Only asked for Spanish nouns, along with article questions.
+    pub fn with_plural_article(&self) -> Option<String> {
+        if self.language != Language::Spanish {
+            return None;
+        }
+        let article: &str = match self.gender {
+            Gender::Feminine => "las",
+            Gender::Any => "los/las",
+            Gender::Masculine | Gender::Neuter => "los",
+        };
+        self.plural_form()
+            .map(|plural| format!("{} {}", article, plural))
+    }
+
+    /// Applies "el agua" in Spanish, and elision and "lo" in French and
+    /// Italian ("l'eau", "lo zio").
+    fn singular_article(&self, gender: Gender) -> &str {
+        let first: char = self.word.chars().next().unwrap_or_default();
+        let starts_with_vowel: bool = "aeiouàâéèêíîóôúùœAEIOUÉ".contains(first);
+        let elides: bool = matches!(gender, Gender::Masculine | Gender::Feminine);
+        match (self.language, gender) {
+            (Language::Spanish, Gender::Feminine) if self.stressed_a => "el",
+            (Language::French, _) if elides && (starts_with_vowel || first == 'h') => "l'",
+            (Language::Italian, _) if elides && starts_with_vowel => "l'",
+            (Language::Italian, Gender::Masculine) if takes_lo(&self.word) => "lo",
+            (language, gender) => language.definite_article(gender),
+        }
+    }
+
+    /// Plural from the dataset, or following the regular Spanish rules for
+    /// single words. Other nouns without a stored plural have none.
+    pub fn plural_form(&self) -> Option<String> {
+        if let Some(plural) = &self.plural {
+            return Some(plural.clone());
+        }
+        if self.language != Language::Spanish || !self.word.chars().all(char::is_alphabetic) {
+            return None;
+        }
+        Some(regular_plural(&self.word))
+    }
+
+    pub fn is_correct_answer(&self, exercise_type: ExerciseType, answer: &Answer) -> bool {
+        match (exercise_type, answer) {
+            (ExerciseType::Gender, Answer::Gender(gender)) => self.gender == *gender,
+            (ExerciseType::SingularArticle, Answer::Article(article)) => {
+                self.accepts_article(article, false)
+            }
+            (ExerciseType::PluralArticle, Answer::Article(article)) => {
+                self.accepts_article(article, true)
+            }
+            _ => false,
+        }
+    }
+
+    fn accepts_article(&self, article: &Article, plural: bool) -> bool {
+        if article.is_plural() != plural {
+            return false;
+        }
+        match self.gender {
+            Gender::Any => true,
+            Gender::Masculine | Gender::Neuter => article.is_masculine(),
+            Gender::Feminine => article.is_masculine() == (self.stressed_a && !plural),
+        }
+    }
+}
+
+/// Italian masculine nouns starting with s and a consonant, z, gn, ps, x or y
+/// take "lo".
+fn takes_lo(word: &str) -> bool {
+    let mut chars = word.chars();
+    match (chars.next(), chars.next()) {
+        (Some('s'), Some(second)) => !"aeiou".contains(second),
+        (Some('z' | 'x' | 'y'), _) => true,
+        (Some('g'), Some('n')) | (Some('p'), Some('s')) => true,
+        _ => false,
+    }
+}
+
+fn regular_plural(singular: &str) -> String {
+    let vowel_groups: usize = singular
+        .split(|c: char| !"aeiouáéíóúü".contains(c))
+        .filter(|group| !group.is_empty())
+        .count();
+    let Some(last) = singular.chars().last() else {
+        return singular.to_string();
+    };
+    let stem: &str = &singular[..singular.len() - last.len_utf8()];
+
+    match last {
+        'a' | 'e' | 'i' | 'o' | 'u' | 'á' | 'é' | 'ó' | 'ú' => format!("{}s", singular),
+        'í' => format!("{}es", singular),
+        'z' => format!("{}ces", stem),
+        's' | 'x' if vowel_groups > 1 && !has_accented_last_syllable(stem) => singular.to_string(),
+        'l' | 'r' | 'n' | 'd' | 'j' | 'y' | 's' | 'x' => {
+            format!("{}{}es", remove_last_accent(stem), last)
+        }
+        _ => format!("{}s", singular),
+    }
+}
+
+fn has_accented_last_syllable(stem: &str) -> bool {
+    stem.chars().last().is_some_and(|c| "áéíóú".contains(c))
+}
+
+/// Words stressed on the last syllable lose the accent mark when a syllable
+/// is added ("canción", "canciones"), unless it marks a hiatus ("país",
+/// "países").
+fn remove_last_accent(stem: &str) -> String {
+    let mut chars = stem.chars().rev();
+    let (Some(last), previous) = (chars.next(), chars.next()) else {
+        return stem.to_string();
+    };
+    let is_hiatus: bool = previous.is_some_and(|c| "aeiou".contains(c));
+    let unaccented: char = match last {
+        'á' => 'a',
+        'é' => 'e',
+        'í' if !is_hiatus => 'i',
+        'ó' => 'o',
+        'ú' if !is_hiatus => 'u',
+        _ => return stem.to_string(),
+    };
+    format!("{}{}", &stem[..stem.len() - last.len_utf8()], unaccented)
+}
+
+/// Kind of question asked on a play.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExerciseType {
+    /// Name the gender of the noun.
+    Gender,
+    /// Pick the article for the singular noun.
+    SingularArticle,
+    /// Pick the article for the plural noun.
+    PluralArticle,
+}
+
+impl ExerciseType {
+    pub fn as_str(&self) -> &str {
+        match self {
+            ExerciseType::Gender => "gender",
+            ExerciseType::SingularArticle => "singular_article",
+            ExerciseType::PluralArticle => "plural_article",
+        }
+    }
+}
+
+impl FromStr for ExerciseType {
+    type Err = String;
+
+    fn from_str(exercise_type_str: &str) -> Result<Self, Self::Err> {
+        match exercise_type_str {
+            "gender" => Ok(ExerciseType::Gender),
+            "singular_article" => Ok(ExerciseType::SingularArticle),
+            "plural_article" => Ok(ExerciseType::PluralArticle),
+            _ => Err(format!("Invalid exercise type '{}'", exercise_type_str)),
+        }
+    }
+}
+
+/// How a play ended. Only graded outcomes count towards accuracy.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PlayOutcome {
+    Correct,
+    Incorrect,
+    /// The user asked for the answer.
+    Unknown,
+    Skipped,
+    /// The user moved on to another question or stopped without answering.
+    Abandoned,
+}
+
+impl PlayOutcome {
+    pub fn graded(is_correct: bool) -> Self {
+        if is_correct {
+            PlayOutcome::Correct
+        } else {
+            PlayOutcome::Incorrect
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            PlayOutcome::Correct => "correct",
+            PlayOutcome::Incorrect => "incorrect",
+            PlayOutcome::Unknown => "unknown",
+            PlayOutcome::Skipped => "skipped",
+            PlayOutcome::Abandoned => "abandoned",
+        }
+    }
+
+    /// Whether the answer was right, for graded outcomes only.
+    pub fn answer(&self) -> Option<bool> {
+        match self {
+            PlayOutcome::Correct => Some(true),
+            PlayOutcome::Incorrect => Some(false),
+            PlayOutcome::Unknown | PlayOutcome::Skipped | PlayOutcome::Abandoned => None,
+        }
+    }
+}
+
+impl FromStr for PlayOutcome {
+    type Err = String;
+
+    fn from_str(outcome_str: &str) -> Result<Self, Self::Err> {
+        match outcome_str {
+            "correct" => Ok(PlayOutcome::Correct),
+            "incorrect" => Ok(PlayOutcome::Incorrect),
+            "unknown" => Ok(PlayOutcome::Unknown),
+            "skipped" => Ok(PlayOutcome::Skipped),
+            "abandoned" => Ok(PlayOutcome::Abandoned),
+            _ => Err(format!("Invalid play outcome '{}'", outcome_str)),
+        }
+    }
+}
+
+/// Answer given to a play, matching its exercise type.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Answer {
+    Gender(Gender),
+    Article(Article),
+}
+
+impl Answer {
+    /// Reads an answer to an exercise of the given type from its `as_str`
+    /// form.
+    pub fn parse(exercise_type: ExerciseType, answer_str: &str) -> Option<Answer> {
+        match exercise_type {
+            ExerciseType::Gender => answer_str.parse().ok().map(Answer::Gender),
+            ExerciseType::SingularArticle | ExerciseType::PluralArticle => {
+                answer_str.parse().ok().map(Answer::Article)
+            }
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            Answer::Gender(gender) => gender.as_str(),
+            Answer::Article(article) => article.as_str(),
+        }
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Gender(gender) => write!(f, "{}", gender),
+            Answer::Article(article) => write!(f, "{}", article),
+        }
+    }
+}
+
+/// Kind of questions a user wants to be asked.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum QuizMode {
+    Gender,
+    Article,
+    Mixed,
+}
+
+impl QuizMode {
+    pub const ALL: [QuizMode; 3] = [QuizMode::Gender, QuizMode::Article, QuizMode::Mixed];
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            QuizMode::Gender => "gender",
+            QuizMode::Article => "article",
+            QuizMode::Mixed => "mixed",
+        }
+    }
+}
+
+impl FromStr for QuizMode {
+    type Err = String;
+
+    fn from_str(quiz_mode_str: &str) -> Result<Self, Self::Err> {
+        match quiz_mode_str {
+            "gender" => Ok(QuizMode::Gender),
+            "article" => Ok(QuizMode::Article),
+            "mixed" => Ok(QuizMode::Mixed),
+            _ => Err(format!("Invalid quiz mode '{}'", quiz_mode_str)),
+        }
+    }
+}
+
+impl fmt::Display for QuizMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label: &str = match self {
+            QuizMode::Gender => "Gender",
+            QuizMode::Article => "Articles",
+            QuizMode::Mixed => "Mixed",
+        };
+        write!(f, "{}", label)
+    }
+}
+
+/// Language the bot talks to the user in.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum UiLanguage {
+    English,
+    Ukrainian,
+    German,
+    Portuguese,
+}
+
+impl UiLanguage {
+    pub const ALL: [UiLanguage; 4] = [
+        UiLanguage::English,
+        UiLanguage::Ukrainian,
+        UiLanguage::German,
+        UiLanguage::Portuguese,
+    ];
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            UiLanguage::English => "en",
+            UiLanguage::Ukrainian => "uk",
+            UiLanguage::German => "de",
+            UiLanguage::Portuguese => "pt",
+        }
+    }
+
+    /// Interface language matching an IETF language tag such as `pt-BR`, as
+    /// reported by Telegram, if it is supported.
+    pub fn from_language_code(language_code: &str) -> Option<UiLanguage> {
+        let primary: &str = language_code.split(['-', '_']).next().unwrap_or_default();
+        primary.to_lowercase().parse().ok()
+    }
+}
+
+impl FromStr for UiLanguage {
+    type Err = String;
+
+    fn from_str(language_str: &str) -> Result<Self, Self::Err> {
+        match language_str {
+            "en" => Ok(UiLanguage::English),
+            "uk" => Ok(UiLanguage::Ukrainian),
+            "de" => Ok(UiLanguage::German),
+            "pt" => Ok(UiLanguage::Portuguese),
+            _ => Err(format!("Invalid UI language '{}'", language_str)),
+        }
+    }
+}
+
+/// Native name of the language, so it can be recognised whatever the
+/// current one is.
+impl fmt::Display for UiLanguage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label: &str = match self {
+            UiLanguage::English => "English",
+            UiLanguage::Ukrainian => "Українська",
+            UiLanguage::German => "Deutsch",
+            UiLanguage::Portuguese => "Português",
+        };
+        write!(f, "{}", label)
+    }
+}
+
+/// Level of a learner, bounding the nouns played by their frequency rank.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Difficulty {
+    Beginner,
+    Intermediate,
+    Advanced,
+    Expert,
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 4] = [
+        Difficulty::Beginner,
+        Difficulty::Intermediate,
+        Difficulty::Advanced,
+        Difficulty::Expert,
+    ];
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            Difficulty::Beginner => "beginner",
+            Difficulty::Intermediate => "intermediate",
+            Difficulty::Advanced => "advanced",
+            Difficulty::Expert => "expert",
+        }
+    }
+
+    /// Highest frequency rank played at this level, `None` meaning all nouns.
+    pub fn max_rank(&self) -> Option<u32> {
+        match self {
+            Difficulty::Beginner => Some(100),
+            Difficulty::Intermediate => Some(500),
+            Difficulty::Advanced => Some(1000),
+            Difficulty::Expert => None,
+        }
+    }
+
+    /// Highest frequency rank of the level below, so nouns ranked above it
+    /// are the ones this level introduces.
+    pub fn min_rank(&self) -> u32 {
+        match self {
+            Difficulty::Beginner => 0,
+            Difficulty::Intermediate => 100,
+            Difficulty::Advanced => 500,
+            Difficulty::Expert => 1000,
+        }
+    }
+
+    pub fn next_level(&self) -> Option<Difficulty> {
+        match self {
+            Difficulty::Beginner => Some(Difficulty::Intermediate),
+            Difficulty::Intermediate => Some(Difficulty::Advanced),
+            Difficulty::Advanced => Some(Difficulty::Expert),
+            Difficulty::Expert => None,
+        }
+    }
+}
+
+impl FromStr for Difficulty {
+    type Err = String;
+
+    fn from_str(difficulty_str: &str) -> Result<Self, Self::Err> {
+        match difficulty_str {
+            "beginner" => Ok(Difficulty::Beginner),
+            "intermediate" => Ok(Difficulty::Intermediate),
+            "advanced" => Ok(Difficulty::Advanced),
+            "expert" => Ok(Difficulty::Expert),
+            _ => Err(format!("Invalid difficulty '{}'", difficulty_str)),
+        }
+    }
+}
+
+impl fmt::Display for Difficulty {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label: &str = match self {
+            Difficulty::Beginner => "Beginner",
+            Difficulty::Intermediate => "Intermediate",
+            Difficulty::Advanced => "Advanced",
+            Difficulty::Expert => "Expert",
+        };
+        write!(f, "{}", label)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct UserSettings {
+    pub quiz_mode: QuizMode,
+    pub show_correct_answer: bool,
+    /// Answers to give every day.
+    pub daily_goal: u32,
+    /// Whether a missed day now and then keeps the daily streak going.
+    pub streak_freeze: bool,
+    /// Minutes after local midnight to send a reminder at, if any.
+    pub reminder_time: Option<u16>,
+    /// Fixed offset of the local time from UTC, not following daylight
+    /// saving time.
+    pub utc_offset_minutes: i32,
+    pub ui_language: UiLanguage,
+    pub difficulty: Difficulty,
+    /// Language the nouns are asked in.
+    pub language: Language,
+}
+
+impl Default for UserSettings {
+    fn default() -> Self {
+        UserSettings {
+            quiz_mode: QuizMode::Gender,
+            show_correct_answer: true,
+            daily_goal: 20,
+            streak_freeze: false,
+            reminder_time: None,
+            utc_offset_minutes: 0,
+            ui_language: UiLanguage::English,
+            difficulty: Difficulty::Beginner,
+            language: Language::Spanish,
+        }
+    }
+}
+
+impl UserSettings {
+    /// Formats the timezone as `UTC+01:00`.
+    pub fn utc_offset(&self) -> String {
+        let sign: char = if self.utc_offset_minutes < 0 {
+            '-'
+        } else {
+            '+'
+        };
+        let minutes: u32 = self.utc_offset_minutes.unsigned_abs();
+        format!("UTC{}{:02}:{:02}", sign, minutes / 60, minutes % 60)
+    }
+
+    /// Formats the reminder time as `19:00`, if one is set.
+    pub fn reminder(&self) -> Option<String> {
+        self.reminder_time
+            .map(|minutes| format!("{:02}:{:02}", minutes / 60, minutes % 60))
+    }
+
+    /// Days since the Unix epoch in the user's timezone.
+    pub fn local_day(&self, time: SystemTime) -> i64 {
+        self.local_seconds(time).div_euclid(SECONDS_PER_DAY)
+    }
+
+    /// Minutes after midnight in the user's timezone.
+    pub fn local_minute(&self, time: SystemTime) -> u16 {
+        (self.local_seconds(time).rem_euclid(SECONDS_PER_DAY) / 60) as u16
+    }
+
+    fn local_seconds(&self, time: SystemTime) -> i64 {
+        let seconds: i64 = time
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs() as i64);
+        seconds + i64::from(self.utc_offset_minutes) * 60
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct User {
     pub id: i32,
     pub telegram_user_id: u64,
+    pub settings: UserSettings,
+}
+
+/// User with a reminder set, with when they were last reminded and when they
+/// last answered a question.
+#[derive(Debug, Clone)]
+pub struct ReminderCandidate {
+    pub user: User,
+    pub last_reminded_at: Option<SystemTime>,
+    pub last_answered_at: Option<SystemTime>,
 }
 
 #[derive(Debug, Clone)]
@@ -35,5 +821,181 @@ pub struct UserPlay {
     pub id: i32,
     pub user_id: i32,
     pub noun_id: i32,
+    pub exercise_type: ExerciseType,
+    /// Whether a graded answer was right.
     pub answer: Option<bool>,
+    /// How the play ended, `None` while it is still waiting for an answer.
+    pub outcome: Option<PlayOutcome>,
+    /// Answer the user gave, `None` until they did and for plays ended
+    /// without one or answered before answers were recorded.
+    pub chosen_answer: Option<Answer>,
+    /// Text the answer was typed as, `None` when it was picked from the
+    /// choices.
+    pub answer_text: Option<String>,
+    /// Milliseconds between asking the question and receiving the answer.
+    pub latency_ms: Option<u32>,
+}
+
+/// How a user ended a play.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlayResponse {
+    pub outcome: PlayOutcome,
+    /// Answer picked or typed, `None` when the play ended without one.
+    pub chosen_answer: Option<Answer>,
+    /// Text the answer was typed as, `None` when it was picked from the
+    /// choices.
+    pub answer_text: Option<String>,
+}
+
+/// Progress through a /mistakes review, kept between answers. Nouns missed
+/// again go back to the end of the queue, so the review ends once each was
+/// answered right.
+#[derive(Debug, Clone)]
+pub struct MistakesSession {
+    /// Nouns left to ask, the next one first.
+    pub queue: VecDeque<i32>,
+    pub count: AnswerCount,
+    /// Nouns missed during the review and not answered right since, with
+    /// their article.
+    pub still_missed: Vec<String>,
+}
+
+/// Stretch of play opened by an answer and closed by /start, /stop or
+/// inactivity.
+#[derive(Debug, Clone)]
+pub struct Session {
+    pub id: i32,
+    pub user_id: i32,
+    /// First play answered in the session.
+    pub first_play_id: i32,
+    pub last_active_at: SystemTime,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct AnswerCount {
+    pub answered: u32,
+    pub correct: u32,
+}
+
+impl AnswerCount {
+    pub fn accuracy(&self) -> f64 {
+        if self.answered == 0 {
+            return 0.0;
+        }
+        f64::from(self.correct) * 100.0 / f64::from(self.answered)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct UserStats {
+    pub overall: AnswerCount,
+    pub by_gender: Vec<(Gender, AnswerCount)>,
+    /// Wrong genders picked, most frequent first.
+    pub gender_mistakes: Vec<GenderMistake>,
+    pub answered_today: u32,
+    pub current_streak: u32,
+}
+
+/// Times nouns of a gender were answered with a given one: a mistake when
+/// the two differ, and one cell of the /weak confusion matrix.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GenderMistake {
+    pub gender: Gender,
+    pub chosen: Gender,
+    pub times: u32,
+}
+
+/// Answers a user gave on one noun.
+#[derive(Debug, Clone)]
+pub struct NounAccuracy {
+    pub noun_id: i32,
+    pub word: String,
+    pub count: AnswerCount,
+}
+
+/// Answers every user gave on a noun, as of the last refresh.
+#[derive(Debug, Clone)]
+pub struct NounDifficulty {
+    pub noun_id: i32,
+    pub count: AnswerCount,
+    /// Mean time taken to answer, over the answers it was recorded for.
+    pub mean_latency_ms: Option<u32>,
+}
+
+#[derive(Debug, Clone)]
+pub struct NounReview {
+    pub user_id: i32,
+    pub noun_id: i32,
+    pub repetitions: u32,
+    pub interval_minutes: u32,
+    pub ease_factor: f64,
+    pub due_at: SystemTime,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn regular_plural_follows_the_ending() {
+        let cases: [(&str, &str); 20] = [
+            // Vowels take -s, except a stressed -í.
+            ("casa", "casas"),
+            ("coche", "coches"),
+            ("día", "días"),
+            ("sofá", "sofás"),
+            ("café", "cafés"),
+            ("tabú", "tabús"),
+            ("rubí", "rubíes"),
+            // -z turns into -ces.
+            ("luz", "luces"),
+            ("lápiz", "lápices"),
+            // Consonants take -es.
+            ("ciudad", "ciudades"),
+            ("árbol", "árboles"),
+            ("ley", "leyes"),
+            ("reloj", "relojes"),
+            ("mes", "meses"),
+            // The accent of a stressed last syllable is dropped.
+            ("camión", "camiones"),
+            ("autobús", "autobuses"),
+            // Unstressed -s and -x are invariable.
+            ("lunes", "lunes"),
+            ("crisis", "crisis"),
+            ("tórax", "tórax"),
+            // A stressed -í or -ú in hiatus keeps its accent.
+            ("país", "países"),
+        ];
+        for (singular, plural) in cases {
+            assert_eq!(regular_plural(singular), plural, "plural of {}", singular);
+        }
+    }
+
+    #[test]
+    fn remove_last_accent_keeps_hiatus() {
+        let cases: [(&str, &str); 6] = [
+            ("canció", "cancio"),
+            ("alemá", "alema"),
+            ("inglé", "ingle"),
+            ("paí", "paí"),
+            ("baú", "baú"),
+            ("mar", "mar"),
+        ];
+        for (stem, unaccented) in cases {
+            assert_eq!(remove_last_accent(stem), unaccented, "stem {}", stem);
+        }
+    }
+
+    #[test]
+    fn has_accented_last_syllable_looks_at_the_last_letter() {
+        let cases: [(&str, bool); 4] = [
+            ("autobú", true),
+            ("inglé", true),
+            ("lune", false),
+            ("", false),
+        ];
+        for (stem, accented) in cases {
+            assert_eq!(has_accented_last_syllable(stem), accented, "stem {}", stem);
+        }
+    }
 }
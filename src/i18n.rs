@@ -0,0 +1,149 @@
+use crate::domain::{Difficulty, Gender, Language, QuizMode, UiLanguage};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::OnceLock;
+
+/// Message catalogue of each interface language, one `key = value` per line.
+/// Values use `\n` for line breaks and `{name}` for the arguments filled in
+/// by [`format`]. Lines starting with `#` are comments.
+const CATALOGUE_FILES: [(UiLanguage, &str); 4] = [
+    (UiLanguage::English, include_str!("../locales/en.txt")),
+    (UiLanguage::Ukrainian, include_str!("../locales/uk.txt")),
+    (UiLanguage::German, include_str!("../locales/de.txt")),
+    (UiLanguage::Portuguese, include_str!("../locales/pt.txt")),
+];
+
+/// Commands registered with the chat platform, described by the
+/// `command-<name>` messages.
+pub const COMMANDS: [&str; 13] = [
+    "start", "stop", "help", "stats", "streak", "reveal", "mode", "language", "lang", "settings",
+    "mistakes", "why", "weak",
+];
+
+type Catalogue = HashMap<&'static str, String>;
+
+fn catalogues() -> &'static Vec<(UiLanguage, Catalogue)> {
+    static CATALOGUES: OnceLock<Vec<(UiLanguage, Catalogue)>> = OnceLock::new();
+    CATALOGUES.get_or_init(|| {
+        CATALOGUE_FILES
+            .iter()
+            .map(|(language, content)| (*language, parse(content)))
+            .collect()
+    })
+}
+
+fn parse(content: &'static str) -> Catalogue {
+    content
+        .lines()
+        .filter(|line| !line.trim().is_empty() && !line.starts_with('#'))
+        .filter_map(|line| line.split_once(" = "))
+        .map(|(key, value)| (key.trim(), value.trim_end().replace("\\n", "\n")))
+        .collect()
+}
+
+fn catalogue(language: UiLanguage) -> &'static Catalogue {
+    let catalogues = catalogues();
+    let (_, catalogue) = catalogues
+        .iter()
+        .find(|(catalogue_language, _)| *catalogue_language == language)
+        .unwrap_or(&catalogues[0]);
+    catalogue
+}
+
+/// Message of the given key, falling back to English when the language lacks
+/// it and to the key itself when no catalogue has it.
+pub fn text(language: UiLanguage, key: &str) -> String {
+    catalogue(language)
+        .get(key)
+        .or_else(|| catalogue(UiLanguage::English).get(key))
+        .cloned()
+        .unwrap_or_else(|| key.to_string())
+}
+
+/// Message of the given key with its `{name}` placeholders filled in.
+pub fn format(language: UiLanguage, key: &str, args: &[(&str, &dyn fmt::Display)]) -> String {
+    args.iter()
+        .fold(text(language, key), |message, (name, value)| {
+            message.replace(&format!("{{{}}}", name), &value.to_string())
+        })
+}
+
+/// Message of the `<key>-<category>` key matching the plural category of
+/// `count`, which fills the `{count}` placeholder.
+pub fn plural(
+    language: UiLanguage,
+    key: &str,
+    count: u32,
+    args: &[(&str, &dyn fmt::Display)],
+) -> String {
+    let plural_key: String = format!("{}-{}", key, plural_category(language, count));
+    let plural_key: String = if catalogue(language).contains_key(plural_key.as_str()) {
+        plural_key
+    } else {
+        format!("{}-other", key)
+    };
+    let mut all_args: Vec<(&str, &dyn fmt::Display)> = vec![("count", &count)];
+    all_args.extend_from_slice(args);
+    format(language, &plural_key, &all_args)
+}
+
+/// Ukrainian tells apart one, a few and many; the other languages only one
+/// and many, the latter being `other`.
+fn plural_category(language: UiLanguage, count: u32) -> &'static str {
+    match language {
+        UiLanguage::Ukrainian => match (count % 10, count % 100) {
+            (1, last_two) if last_two != 11 => "one",
+            (2..=4, last_two) if !(12..=14).contains(&last_two) => "few",
+            _ => "other",
+        },
+        _ if count == 1 => "one",
+        _ => "other",
+    }
+}
+
+/// Keys of the English catalogue the language has no message for.
+pub fn missing_keys(language: UiLanguage) -> Vec<&'static str> {
+    let translated: &Catalogue = catalogue(language);
+    let mut missing: Vec<&'static str> = catalogue(UiLanguage::English)
+        .keys()
+        .filter(|key| !translated.contains_key(*key))
+        .copied()
+        .collect();
+    missing.sort();
+    missing
+}
+
+/// Name of the gender as offered on answer buttons, such as "Feminine".
+pub fn gender(language: UiLanguage, gender: Gender) -> String {
+    text(language, &format!("gender-{}", gender.as_str()))
+}
+
+/// Name of the gender in a sentence, such as "la casa — feminine".
+pub fn gender_in_sentence(language: UiLanguage, gender: Gender) -> String {
+    text(language, &format!("noun-gender-{}", gender.as_str()))
+}
+
+pub fn quiz_mode(language: UiLanguage, quiz_mode: QuizMode) -> String {
+    text(language, &format!("quiz-mode-{}", quiz_mode.as_str()))
+}
+
+pub fn difficulty(language: UiLanguage, difficulty: Difficulty) -> String {
+    text(language, &format!("difficulty-{}", difficulty.as_str()))
+}
+
+/// Nouns a level plays, such as "top 100 nouns".
+pub fn scope(language: UiLanguage, difficulty: Difficulty) -> String {
+    match difficulty.max_rank() {
+        Some(max_rank) => format(language, "scope-top", &[("count", &max_rank)]),
+        None => text(language, "scope-all"),
+    }
+}
+
+/// Name of a language nouns are learnt in.
+pub fn learning_language(language: UiLanguage, learning: Language) -> String {
+    text(language, &format!("language-{}", learning.as_str()))
+}
+
+pub fn on_off(language: UiLanguage, enabled: bool) -> String {
+    text(language, if enabled { "on" } else { "off" })
+}
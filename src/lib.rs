@@ -0,0 +1,5 @@
+pub mod client;
+pub mod domain;
+pub mod i18n;
+pub mod repository;
+pub mod service;
@@ -1,21 +1,117 @@
-mod client;
-mod domain;
-mod repository;
-mod service;
-
-use crate::client::telegram::TelegramClient;
-use crate::repository::migration;
-use crate::service::bot::BotService;
 use dotenv::dotenv;
-use frankenstein::Update;
+use el_la_la::client::chat::ChatEvent;
+use el_la_la::client::telegram::TelegramClient;
+use el_la_la::domain::{Language, Noun, NounDifficulty, UiLanguage};
+use el_la_la::repository::analytics::AnalyticsRepository;
+use el_la_la::repository::loader::{self, LoadReport, NounsLoader};
+use el_la_la::repository::{connector, migration, Repositories};
+use el_la_la::service::bot::BotService;
+use el_la_la::service::{noun_difficulty, scheduler};
+use mysql::Pool;
+use std::env;
 use std::error::Error;
 use std::sync::mpsc::{channel, Receiver, Sender};
+use std::time::SystemTime;
 
 fn main() -> Result<(), Box<dyn Error>> {
     dotenv().ok();
-    migration::run()?;
+    let pool: Pool = connector::new()?;
+    migration::run(&pool)?;
+
+    let args: Vec<String> = env::args().collect();
+    if args.get(1).map(String::as_str) == Some("import-nouns") {
+        let Some(path) = args.get(2) else {
+            return import_bundled_nouns(&pool);
+        };
+        let language: Language = match args.get(3) {
+            Some(language_str) => language_str.parse()?,
+            None => Language::Spanish,
+        };
+        return import_nouns(&pool, path, language);
+    }
+    if args.get(1).map(String::as_str) == Some("noun-difficulty") {
+        let size: u32 = noun_difficulty::parse_report_size(args.get(2).map(String::as_str))?;
+        return print_noun_difficulty(pool, size);
+    }
+    // The datasets are the source of the nouns: the ones seeded by the early
+    // migrations are brought in line with them on every startup.
+    match env::var("NOUNS_CSV_PATH") {
+        Ok(path) => {
+            let language: Language = match env::var("NOUNS_LANGUAGE") {
+                Ok(language_str) => language_str.parse()?,
+                Err(_) => Language::Spanish,
+            };
+            import_nouns(&pool, &path, language)?;
+        }
+        Err(_) => import_bundled_nouns(&pool)?,
+    }
 
-    let (channel_sender, channel_receiver): (Sender<Update>, Receiver<Update>) = channel();
+    let (channel_sender, channel_receiver): (Sender<ChatEvent>, Receiver<ChatEvent>) = channel();
+    scheduler::spawn(channel_sender.clone(), scheduler::TICK_INTERVAL);
+    scheduler::spawn_noun_difficulty_refresh(
+        AnalyticsRepository::new(pool.clone()),
+        noun_difficulty::REFRESH_INTERVAL,
+    );
     let telegram_client: TelegramClient = TelegramClient::new(channel_sender);
-    BotService::new(telegram_client).run(channel_receiver)
+    BotService::new(telegram_client, Repositories::new(pool))
+        .with_admins(admin_user_ids()?)
+        .run(channel_receiver)
+}
+
+/// Telegram ids of the users allowed to run admin commands, separated by
+/// commas in `ADMIN_USER_IDS`.
+fn admin_user_ids() -> Result<Vec<u64>, Box<dyn Error>> {
+    let Ok(ids) = env::var("ADMIN_USER_IDS") else {
+        return Ok(Vec::new());
+    };
+    ids.split(',')
+        .map(str::trim)
+        .filter(|id| !id.is_empty())
+        .map(|id| {
+            id.parse::<u64>()
+                .map_err(|e| format!("Invalid admin user id {}: {}", id, e).into())
+        })
+        .collect()
+}
+
+fn import_nouns(pool: &Pool, path: &str, language: Language) -> Result<(), Box<dyn Error>> {
+    let report: LoadReport = NounsLoader::new(pool.clone()).load(path, language)?;
+    println!("{}", report);
+    Ok(())
+}
+
+fn import_bundled_nouns(pool: &Pool) -> Result<(), Box<dyn Error>> {
+    for language in Language::ALL {
+        println!("{}:", language);
+        import_nouns(pool, &loader::default_path(language), language)?;
+    }
+    Ok(())
+}
+
+/// Refreshes the noun difficulty and prints the hardest and easiest nouns.
+fn print_noun_difficulty(pool: Pool, size: u32) -> Result<(), Box<dyn Error>> {
+    let mut repositories: Repositories = Repositories::new(pool);
+    repositories
+        .analytics
+        .refresh_noun_difficulty(SystemTime::now())?;
+    let min_answers: u32 = noun_difficulty::MIN_ANSWERS;
+    let hardest: Vec<NounDifficulty> = repositories
+        .analytics
+        .get_hardest_nouns(min_answers, size)?;
+    let easiest: Vec<NounDifficulty> = repositories
+        .analytics
+        .get_easiest_nouns(min_answers, size)?;
+    let mut with_nouns = |difficulties: Vec<NounDifficulty>| {
+        difficulties
+            .into_iter()
+            .map(|difficulty| Ok((repositories.nouns.get(difficulty.noun_id)?, difficulty)))
+            .collect::<Result<Vec<(Noun, NounDifficulty)>, Box<dyn Error>>>()
+    };
+    let hardest: Vec<(Noun, NounDifficulty)> = with_nouns(hardest)?;
+    let easiest: Vec<(Noun, NounDifficulty)> = with_nouns(easiest)?;
+    println!(
+        "{}",
+        noun_difficulty::report(UiLanguage::English, &hardest, &easiest)
+    );
+    Ok(())
 }
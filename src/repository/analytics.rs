@@ -0,0 +1,188 @@
+use crate::domain::{AnswerCount, GenderMistake, Language, NounAccuracy, NounDifficulty};
+use crate::repository::connector;
+use crate::repository::user_plays::UNKNOWN_ANSWER;
+use mysql::prelude::Queryable;
+use mysql::{params, Params, Pool, PooledConn};
+use std::error::Error;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Reports over the answers joined with the nouns they were about, for a user
+/// or across all of them, as opposed to the per-request reads and writes of the
+/// other repositories.
+pub struct AnalyticsRepository {
+    pool: Pool,
+}
+
+impl AnalyticsRepository {
+    pub fn new(pool: Pool) -> Self {
+        AnalyticsRepository { pool }
+    }
+
+    fn conn(&self) -> Result<PooledConn, Box<dyn Error>> {
+        connector::get_conn(&self.pool)
+    }
+
+    /// Refreshed noun difficulty with enough answers, sorted by `order_by`.
+    fn get_noun_difficulty(
+        &self,
+        order_by: &str,
+        min_answers: u32,
+        limit: u32,
+    ) -> Result<Vec<NounDifficulty>, Box<dyn Error>> {
+        let statement: String = format!(
+            "\
+            SELECT noun_id, answered, correct, mean_latency_ms \
+            FROM noun_difficulty \
+            WHERE answered >= :min_answers \
+            ORDER BY {} \
+            LIMIT :limit",
+            order_by
+        );
+        let params: Params = params! {
+            "min_answers" => min_answers,
+            "limit" => limit,
+        };
+        let rows: Vec<(i32, u32, u32, Option<u32>)> = self.conn()?.exec(statement, params)?;
+        let difficulties: Vec<NounDifficulty> = rows
+            .into_iter()
+            .map(
+                |(noun_id, answered, correct, mean_latency_ms)| NounDifficulty {
+                    noun_id,
+                    count: AnswerCount { answered, correct },
+                    mean_latency_ms,
+                },
+            )
+            .collect();
+        Ok(difficulties)
+    }
+}
+
+pub trait AnalyticsRepositoryTrait {
+    /// Genders picked on the gender questions about nouns of the language,
+    /// against the nouns' own, for the answers whose choice was recorded.
+    fn count_gender_confusion(
+        &mut self,
+        user_id: i32,
+        language: Language,
+    ) -> Result<Vec<GenderMistake>, Box<dyn Error>>;
+    /// Graded answers on each noun of the language the user answered.
+    fn count_answers_by_noun(
+        &mut self,
+        user_id: i32,
+        language: Language,
+    ) -> Result<Vec<NounAccuracy>, Box<dyn Error>>;
+    /// Recomputes the answers of all users on every noun answered so far.
+    fn refresh_noun_difficulty(&mut self, refreshed_at: SystemTime) -> Result<(), Box<dyn Error>>;
+    /// Nouns with at least `min_answers` answers, least accurate first.
+    fn get_hardest_nouns(
+        &mut self,
+        min_answers: u32,
+        limit: u32,
+    ) -> Result<Vec<NounDifficulty>, Box<dyn Error>>;
+    /// Nouns with at least `min_answers` answers, most accurate first.
+    fn get_easiest_nouns(
+        &mut self,
+        min_answers: u32,
+        limit: u32,
+    ) -> Result<Vec<NounDifficulty>, Box<dyn Error>>;
+}
+
+impl AnalyticsRepositoryTrait for AnalyticsRepository {
+    fn count_gender_confusion(
+        &mut self,
+        user_id: i32,
+        language: Language,
+    ) -> Result<Vec<GenderMistake>, Box<dyn Error>> {
+        let statement: &str = "\
+            SELECT n.gender, p.chosen_answer, COUNT(*) \
+            FROM user_plays p \
+            JOIN nouns n ON n.id = p.noun_id \
+            WHERE p.user_id = :user_id \
+              AND n.language = :language \
+              AND p.exercise_type = 'gender' \
+              AND p.answer IS NOT NULL \
+              AND p.chosen_answer <> :unknown \
+            GROUP BY n.gender, p.chosen_answer \
+            ORDER BY n.gender, p.chosen_answer";
+        let params: Params = params! {
+            "user_id" => user_id,
+            "language" => language.as_str(),
+            "unknown" => UNKNOWN_ANSWER,
+        };
+        let rows: Vec<(String, String, u32)> = self.conn()?.exec(statement, params)?;
+        rows.into_iter()
+            .map(|(gender_str, chosen_str, times)| {
+                Ok(GenderMistake {
+                    gender: gender_str.parse()?,
+                    chosen: chosen_str.parse()?,
+                    times,
+                })
+            })
+            .collect()
+    }
+
+    fn count_answers_by_noun(
+        &mut self,
+        user_id: i32,
+        language: Language,
+    ) -> Result<Vec<NounAccuracy>, Box<dyn Error>> {
+        let statement: &str = "\
+            SELECT n.id, n.word, COUNT(*), CAST(COALESCE(SUM(p.answer), 0) AS UNSIGNED) \
+            FROM user_plays p \
+            JOIN nouns n ON n.id = p.noun_id \
+            WHERE p.user_id = :user_id AND n.language = :language AND p.answer IS NOT NULL \
+            GROUP BY n.id, n.word \
+            ORDER BY n.id";
+        let params: Params = params! {
+            "user_id" => user_id,
+            "language" => language.as_str(),
+        };
+        let rows: Vec<(i32, String, u32, u32)> = self.conn()?.exec(statement, params)?;
+        let accuracies: Vec<NounAccuracy> = rows
+            .into_iter()
+            .map(|(noun_id, word, answered, correct)| NounAccuracy {
+                noun_id,
+                word,
+                count: AnswerCount { answered, correct },
+            })
+            .collect();
+        Ok(accuracies)
+    }
+
+    fn refresh_noun_difficulty(&mut self, refreshed_at: SystemTime) -> Result<(), Box<dyn Error>> {
+        let statement: &str = "\
+            INSERT INTO noun_difficulty \
+                (noun_id, answered, correct, mean_latency_ms, refreshed_at) \
+            SELECT noun_id, COUNT(*), SUM(answer), ROUND(AVG(latency_ms)), \
+                FROM_UNIXTIME(:refreshed_at) \
+            FROM user_plays \
+            WHERE answer IS NOT NULL \
+            GROUP BY noun_id \
+            ON DUPLICATE KEY UPDATE \
+                answered = VALUES(answered), \
+                correct = VALUES(correct), \
+                mean_latency_ms = VALUES(mean_latency_ms), \
+                refreshed_at = VALUES(refreshed_at)";
+        let refreshed_at: u64 = refreshed_at.duration_since(UNIX_EPOCH)?.as_secs();
+        let params: Params = params! {"refreshed_at" => refreshed_at};
+        self.conn()?
+            .exec_drop(statement, params)
+            .map_err(|e| e.into())
+    }
+
+    fn get_hardest_nouns(
+        &mut self,
+        min_answers: u32,
+        limit: u32,
+    ) -> Result<Vec<NounDifficulty>, Box<dyn Error>> {
+        self.get_noun_difficulty("correct / answered, answered DESC", min_answers, limit)
+    }
+
+    fn get_easiest_nouns(
+        &mut self,
+        min_answers: u32,
+        limit: u32,
+    ) -> Result<Vec<NounDifficulty>, Box<dyn Error>> {
+        self.get_noun_difficulty("correct / answered DESC, answered DESC", min_answers, limit)
+    }
+}
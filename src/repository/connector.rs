@@ -1,15 +1,130 @@
-use mysql::{Pool, PooledConn};
-use std::env;
+use mysql::{Opts, OptsBuilder, Pool, PoolConstraints, PoolOpts, PooledConn, SslOpts};
 use std::error::Error;
+use std::path::PathBuf;
+use std::time::Duration;
+use std::{env, fs, thread};
 
-pub fn new() -> Result<PooledConn, Box<dyn Error>> {
-    let database_host: String = env::var("DATABASE_HOST").unwrap_or("127.0.0.1".into());
-    let database_port: String = env::var("DATABASE_PORT").unwrap_or("3306".into());
-    let url: String = format!(
-        "mysql://el-la-la-user:el-la-la-password@{}:{}/el-la-la-db",
-        database_host, database_port
-    );
-    Pool::new(url.as_str())
-        .and_then(|pool| pool.get_conn())
-        .map_err(|e| e.into())
+const CONNECTION_ATTEMPTS: u32 = 3;
+const CONNECTION_RETRY_DELAY: Duration = Duration::from_secs(2);
+
+/// Creates the connection pool shared by every repository. Its size is
+/// bounded by `DATABASE_POOL_MIN` and `DATABASE_POOL_MAX`.
+pub fn new() -> Result<Pool, Box<dyn Error>> {
+    let opts: Opts = database_opts()?;
+    Pool::new(opts).map_err(|e| e.into())
+}
+
+/// Checks a connection out of the pool for a single operation. The pool pings
+/// idle connections before handing them out and replaces dead ones, so this
+/// only has to wait for the database while it is unreachable.
+pub fn get_conn(pool: &Pool) -> Result<PooledConn, Box<dyn Error>> {
+    let mut attempt: u32 = 1;
+    loop {
+        match pool.get_conn() {
+            Ok(conn) => return Ok(conn),
+            Err(e) if attempt < CONNECTION_ATTEMPTS => {
+                eprintln!("Database connection failed (attempt {}): {}", attempt, e);
+                thread::sleep(CONNECTION_RETRY_DELAY);
+                attempt += 1;
+            }
+            Err(e) => return Err(e.into()),
+        }
+    }
+}
+
+/// Builds the connection options either from `DATABASE_URL` or from the
+/// separate `DATABASE_*` variables. Credentials may also be read from a
+/// secret file through their `*_FILE` variant.
+fn database_opts() -> Result<Opts, Box<dyn Error>> {
+    let builder: OptsBuilder = match read_variable("DATABASE_URL")? {
+        Some(url) => {
+            let opts: Opts =
+                Opts::from_url(&url).map_err(|e| format!("Invalid DATABASE_URL: {}", e))?;
+            OptsBuilder::from_opts(opts)
+        }
+        None => {
+            let database_host: String =
+                read_variable("DATABASE_HOST")?.unwrap_or("127.0.0.1".into());
+            let database_port: u16 = read_variable("DATABASE_PORT")?
+                .unwrap_or("3306".into())
+                .parse()
+                .map_err(|e| format!("Invalid DATABASE_PORT: {}", e))?;
+            OptsBuilder::new()
+                .ip_or_hostname(Some(database_host))
+                .tcp_port(database_port)
+                .user(Some(require_variable("DATABASE_USER")?))
+                .pass(Some(require_variable("DATABASE_PASSWORD")?))
+                .db_name(Some(require_variable("DATABASE_NAME")?))
+        }
+    };
+    Ok(builder.ssl_opts(ssl_opts()?).pool_opts(pool_opts()?).into())
+}
+
+fn pool_opts() -> Result<PoolOpts, Box<dyn Error>> {
+    let min: usize = read_number("DATABASE_POOL_MIN", 1)?;
+    let max: usize = read_number("DATABASE_POOL_MAX", 10)?;
+    let constraints: PoolConstraints = PoolConstraints::new(min, max).ok_or_else(|| {
+        format!(
+            "Invalid pool size: DATABASE_POOL_MIN ({}) exceeds DATABASE_POOL_MAX ({})",
+            min, max
+        )
+    })?;
+    Ok(PoolOpts::default().with_constraints(constraints))
+}
+
+/// TLS is enabled with `DATABASE_TLS=true`. `DATABASE_TLS_CA_FILE` adds a
+/// root certificate and `DATABASE_TLS_SKIP_VERIFY=true` accepts any server
+/// certificate.
+fn ssl_opts() -> Result<Option<SslOpts>, Box<dyn Error>> {
+    if !read_flag("DATABASE_TLS")? {
+        return Ok(None);
+    }
+    let root_cert_path: Option<PathBuf> = read_variable("DATABASE_TLS_CA_FILE")?.map(PathBuf::from);
+    let skip_verify: bool = read_flag("DATABASE_TLS_SKIP_VERIFY")?;
+    let ssl_opts: SslOpts = SslOpts::default()
+        .with_root_cert_path(root_cert_path)
+        .with_danger_accept_invalid_certs(skip_verify)
+        .with_danger_skip_domain_validation(skip_verify);
+    Ok(Some(ssl_opts))
+}
+
+fn read_number(name: &str, default: usize) -> Result<usize, Box<dyn Error>> {
+    match read_variable(name)? {
+        Some(value) => value
+            .parse()
+            .map_err(|e| format!("Invalid {} '{}': {}", name, value, e).into()),
+        None => Ok(default),
+    }
+}
+
+fn read_flag(name: &str) -> Result<bool, Box<dyn Error>> {
+    match read_variable(name)?.as_deref() {
+        None | Some("false") | Some("0") => Ok(false),
+        Some("true") | Some("1") => Ok(true),
+        Some(value) => Err(format!("Invalid {} '{}', expected true or false", name, value).into()),
+    }
+}
+
+fn require_variable(name: &str) -> Result<String, Box<dyn Error>> {
+    read_variable(name)?.ok_or_else(|| {
+        format!(
+            "Incomplete database configuration: set DATABASE_URL, or {} (or {}_FILE)",
+            name, name
+        )
+        .into()
+    })
+}
+
+/// Reads `name` from the environment, falling back to the content of the
+/// file pointed to by `name_FILE`.
+fn read_variable(name: &str) -> Result<Option<String>, Box<dyn Error>> {
+    if let Ok(value) = env::var(name) {
+        return Ok(Some(value));
+    }
+    match env::var(format!("{}_FILE", name)) {
+        Ok(path) => fs::read_to_string(&path)
+            .map(|content| Some(content.trim_end().to_string()))
+            .map_err(|e| format!("Cannot read {}_FILE '{}': {}", name, path, e).into()),
+        Err(_) => Ok(None),
+    }
 }
@@ -0,0 +1,759 @@
+use crate::domain::{
+    Answer, AnswerCount, ExerciseType, Gender, GenderMistake, Language, MistakesSession, Noun,
+    NounAccuracy, NounDifficulty, NounReview, PlayOutcome, PlayResponse, ReminderCandidate,
+    Session, User, UserPlay, UserSettings,
+};
+use crate::repository::analytics::AnalyticsRepositoryTrait;
+use crate::repository::mistake_sessions::MistakeSessionsRepositoryTrait;
+use crate::repository::noun_reviews::NounReviewsRepositoryTrait;
+use crate::repository::nouns::NounsRepositoryTrait;
+use crate::repository::sessions::SessionsRepositoryTrait;
+use crate::repository::user_plays::UserPlaysRepositoryTrait;
+use crate::repository::users::UsersRepositoryTrait;
+use crate::repository::Repositories;
+use rand::seq::SliceRandom;
+use std::cell::RefCell;
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BTreeMap, HashMap};
+use std::error::Error;
+use std::rc::Rc;
+use std::time::{Duration, SystemTime};
+
+#[derive(Debug, Clone)]
+struct StoredPlay {
+    play: UserPlay,
+    timestamp: SystemTime,
+}
+
+#[derive(Debug, Clone)]
+struct StoredSession {
+    session: Session,
+    ended_at: Option<SystemTime>,
+}
+
+#[derive(Debug, Default)]
+struct Tables {
+    users: Vec<User>,
+    last_reminded_at: HashMap<i32, SystemTime>,
+    nouns: Vec<Noun>,
+    plays: Vec<StoredPlay>,
+    last_play_id: i32,
+    reviews: Vec<NounReview>,
+    sessions: Vec<StoredSession>,
+    noun_difficulty: Vec<NounDifficulty>,
+    mistake_sessions: HashMap<i32, MistakesSession>,
+}
+
+/// Storage implementing every repository trait on plain vectors, mirroring
+/// the MariaDB queries closely enough to run the bot without a database.
+/// Clones share the same tables, so the repositories handed to a service can
+/// be inspected afterwards. Unlike the database, unseen nouns are picked in id
+/// order to keep scenarios deterministic.
+#[derive(Debug, Clone, Default)]
+pub struct InMemoryDatabase {
+    tables: Rc<RefCell<Tables>>,
+}
+
+impl InMemoryDatabase {
+    pub fn with_nouns(nouns: Vec<Noun>) -> Self {
+        let database = Self::default();
+        database.tables.borrow_mut().nouns = nouns;
+        database
+    }
+
+    pub fn repositories(&self) -> Repositories {
+        Repositories {
+            users: Box::new(self.clone()),
+            nouns: Box::new(self.clone()),
+            user_plays: Box::new(self.clone()),
+            noun_reviews: Box::new(self.clone()),
+            sessions: Box::new(self.clone()),
+            analytics: Box::new(self.clone()),
+            mistake_sessions: Box::new(self.clone()),
+        }
+    }
+
+    pub fn users(&self) -> Vec<User> {
+        self.tables.borrow().users.clone()
+    }
+
+    pub fn plays(&self) -> Vec<UserPlay> {
+        let tables = self.tables.borrow();
+        tables
+            .plays
+            .iter()
+            .map(|stored| stored.play.clone())
+            .collect()
+    }
+
+    pub fn reviews(&self) -> Vec<NounReview> {
+        let tables = self.tables.borrow();
+        tables.reviews.clone()
+    }
+
+    fn is_within(noun: &Noun, max_rank: Option<u32>) -> bool {
+        max_rank.map_or(true, |max_rank| noun.frequency_rank <= max_rank)
+    }
+
+    fn answered_plays(&self, user_id: i32) -> Vec<StoredPlay> {
+        let tables = self.tables.borrow();
+        tables
+            .plays
+            .iter()
+            .filter(|stored| stored.play.user_id == user_id && stored.play.answer.is_some())
+            .cloned()
+            .collect()
+    }
+
+    /// Refreshed noun difficulty with enough answers, most answered first
+    /// among equally accurate nouns, limited after sorting by accuracy.
+    fn ranked_difficulty(
+        &self,
+        min_answers: u32,
+        limit: u32,
+        easiest_first: bool,
+    ) -> Vec<NounDifficulty> {
+        let mut difficulties: Vec<NounDifficulty> = self
+            .tables
+            .borrow()
+            .noun_difficulty
+            .iter()
+            .filter(|difficulty| difficulty.count.answered >= min_answers)
+            .cloned()
+            .collect();
+        difficulties.sort_by(|a, b| {
+            let a_ratio: u64 = u64::from(a.count.correct) * u64::from(b.count.answered);
+            let b_ratio: u64 = u64::from(b.count.correct) * u64::from(a.count.answered);
+            let by_accuracy: Ordering = if easiest_first {
+                b_ratio.cmp(&a_ratio)
+            } else {
+                a_ratio.cmp(&b_ratio)
+            };
+            by_accuracy.then(b.count.answered.cmp(&a.count.answered))
+        });
+        difficulties.truncate(limit as usize);
+        difficulties
+    }
+
+    /// Answered plays on nouns of the language, with their noun.
+    fn answered_plays_in(&self, user_id: i32, language: Language) -> Vec<(StoredPlay, Noun)> {
+        let nouns: Vec<Noun> = self.tables.borrow().nouns.clone();
+        self.answered_plays(user_id)
+            .into_iter()
+            .filter_map(|stored| {
+                let noun: &Noun = nouns.iter().find(|noun| noun.id == stored.play.noun_id)?;
+                Some((stored, noun.clone())).filter(|(_, noun)| noun.language == language)
+            })
+            .collect()
+    }
+}
+
+impl UsersRepositoryTrait for InMemoryDatabase {
+    fn get(&mut self, telegram_user_id: u64) -> Result<Option<User>, Box<dyn Error>> {
+        let tables = self.tables.borrow();
+        Ok(tables
+            .users
+            .iter()
+            .find(|user| user.telegram_user_id == telegram_user_id)
+            .cloned())
+    }
+
+    fn insert(&mut self, user: &User) -> Result<(), Box<dyn Error>> {
+        let mut tables = self.tables.borrow_mut();
+        if tables
+            .users
+            .iter()
+            .any(|existing| existing.telegram_user_id == user.telegram_user_id)
+        {
+            return Err("Duplicate entry for key 'idx_telegram_user_id'".into());
+        }
+        let id: i32 = tables.users.len() as i32 + 1;
+        tables.users.push(User { id, ..user.clone() });
+        Ok(())
+    }
+
+    fn update_settings(
+        &mut self,
+        user_id: i32,
+        settings: &UserSettings,
+    ) -> Result<(), Box<dyn Error>> {
+        let mut tables = self.tables.borrow_mut();
+        if let Some(user) = tables.users.iter_mut().find(|user| user.id == user_id) {
+            user.settings = settings.clone();
+        }
+        Ok(())
+    }
+
+    fn get_reminder_candidates(&mut self) -> Result<Vec<ReminderCandidate>, Box<dyn Error>> {
+        let tables = self.tables.borrow();
+        Ok(tables
+            .users
+            .iter()
+            .filter(|user| user.settings.reminder_time.is_some())
+            .map(|user| ReminderCandidate {
+                user: user.clone(),
+                last_reminded_at: tables.last_reminded_at.get(&user.id).copied(),
+                last_answered_at: tables
+                    .plays
+                    .iter()
+                    .filter(|stored| stored.play.user_id == user.id && stored.play.answer.is_some())
+                    .map(|stored| stored.timestamp)
+                    .max(),
+            })
+            .collect())
+    }
+
+    fn update_last_reminded_at(
+        &mut self,
+        user_id: i32,
+        reminded_at: SystemTime,
+    ) -> Result<(), Box<dyn Error>> {
+        self.tables
+            .borrow_mut()
+            .last_reminded_at
+            .insert(user_id, reminded_at);
+        Ok(())
+    }
+}
+
+impl NounsRepositoryTrait for InMemoryDatabase {
+    fn get(&mut self, id: i32) -> Result<Noun, Box<dyn Error>> {
+        let tables = self.tables.borrow();
+        tables
+            .nouns
+            .iter()
+            .find(|noun| noun.id == id)
+            .cloned()
+            .ok_or("No nouns found in the database".into())
+    }
+
+    fn get_random(
+        &mut self,
+        language: Language,
+        max_rank: Option<u32>,
+    ) -> Result<Noun, Box<dyn Error>> {
+        let tables = self.tables.borrow();
+        let nouns: Vec<&Noun> = tables
+            .nouns
+            .iter()
+            .filter(|noun| noun.language == language && Self::is_within(noun, max_rank))
+            .collect();
+        nouns
+            .choose(&mut rand::thread_rng())
+            .map(|noun| (*noun).clone())
+            .ok_or("No nouns found in the database".into())
+    }
+
+    fn get_most_overdue(
+        &mut self,
+        user_id: i32,
+        language: Language,
+    ) -> Result<Option<Noun>, Box<dyn Error>> {
+        let now: SystemTime = SystemTime::now();
+        let tables = self.tables.borrow();
+        let find_noun = |noun_id: i32| tables.nouns.iter().find(|noun| noun.id == noun_id);
+        Ok(tables
+            .reviews
+            .iter()
+            .filter(|review| review.user_id == user_id && review.due_at <= now)
+            .filter_map(|review| find_noun(review.noun_id).map(|noun| (review, noun)))
+            .filter(|(_, noun)| noun.language == language)
+            .min_by_key(|(review, _)| review.due_at)
+            .map(|(_, noun)| noun.clone()))
+    }
+
+    fn get_random_unseen(
+        &mut self,
+        user_id: i32,
+        language: Language,
+        max_rank: Option<u32>,
+    ) -> Result<Option<Noun>, Box<dyn Error>> {
+        let tables = self.tables.borrow();
+        let is_seen = |noun: &Noun| {
+            tables
+                .reviews
+                .iter()
+                .any(|review| review.user_id == user_id && review.noun_id == noun.id)
+        };
+        let is_skipped = |noun: &Noun| {
+            tables.plays.iter().any(|stored| {
+                stored.play.user_id == user_id
+                    && stored.play.noun_id == noun.id
+                    && stored.play.outcome == Some(PlayOutcome::Skipped)
+            })
+        };
+        Ok(tables
+            .nouns
+            .iter()
+            .find(|noun| {
+                noun.language == language
+                    && Self::is_within(noun, max_rank)
+                    && !is_seen(noun)
+                    && !is_skipped(noun)
+            })
+            .cloned())
+    }
+}
+
+impl UserPlaysRepositoryTrait for InMemoryDatabase {
+    fn get(&mut self, play_id: i32) -> Result<Option<UserPlay>, Box<dyn Error>> {
+        let tables = self.tables.borrow();
+        Ok(tables
+            .plays
+            .iter()
+            .find(|stored| stored.play.id == play_id)
+            .map(|stored| stored.play.clone()))
+    }
+
+    fn get_last(&mut self, user_id: i32) -> Result<Option<UserPlay>, Box<dyn Error>> {
+        let tables = self.tables.borrow();
+        Ok(tables
+            .plays
+            .iter()
+            .rev()
+            .find(|stored| stored.play.user_id == user_id && stored.play.outcome.is_none())
+            .map(|stored| stored.play.clone()))
+    }
+
+    fn get_last_answered(&mut self, user_id: i32) -> Result<Option<UserPlay>, Box<dyn Error>> {
+        let tables = self.tables.borrow();
+        Ok(tables
+            .plays
+            .iter()
+            .rev()
+            .find(|stored| stored.play.user_id == user_id && stored.play.answer.is_some())
+            .map(|stored| stored.play.clone()))
+    }
+
+    fn insert(
+        &mut self,
+        user_id: i32,
+        noun_id: i32,
+        exercise_type: ExerciseType,
+    ) -> Result<i32, Box<dyn Error>> {
+        let mut tables = self.tables.borrow_mut();
+        tables.last_play_id += 1;
+        let id: i32 = tables.last_play_id;
+        tables.plays.push(StoredPlay {
+            play: UserPlay {
+                id,
+                user_id,
+                noun_id,
+                exercise_type,
+                answer: None,
+                outcome: None,
+                chosen_answer: None,
+                answer_text: None,
+                latency_ms: None,
+            },
+            timestamp: SystemTime::now(),
+        });
+        Ok(id)
+    }
+
+    fn update(&mut self, play_id: i32, response: &PlayResponse) -> Result<(), Box<dyn Error>> {
+        let mut tables = self.tables.borrow_mut();
+        if let Some(stored) = tables
+            .plays
+            .iter_mut()
+            .find(|stored| stored.play.id == play_id)
+        {
+            let latency: Duration = stored.timestamp.elapsed().unwrap_or_default();
+            stored.play.answer = response.outcome.answer();
+            stored.play.outcome = Some(response.outcome);
+            stored.play.chosen_answer = response.chosen_answer.clone();
+            stored.play.answer_text = response.answer_text.clone();
+            stored.play.latency_ms = Some(latency.as_millis() as u32);
+        }
+        Ok(())
+    }
+
+    fn get_answered_since(
+        &mut self,
+        user_id: i32,
+        first_play_id: i32,
+    ) -> Result<Vec<UserPlay>, Box<dyn Error>> {
+        Ok(self
+            .answered_plays(user_id)
+            .into_iter()
+            .filter(|stored| stored.play.id >= first_play_id)
+            .map(|stored| stored.play)
+            .collect())
+    }
+
+    fn count_answers(&mut self, user_id: i32) -> Result<AnswerCount, Box<dyn Error>> {
+        let plays: Vec<StoredPlay> = self.answered_plays(user_id);
+        Ok(AnswerCount {
+            answered: plays.len() as u32,
+            correct: plays
+                .iter()
+                .filter(|stored| stored.play.answer == Some(true))
+                .count() as u32,
+        })
+    }
+
+    fn count_answers_by_gender(
+        &mut self,
+        user_id: i32,
+    ) -> Result<Vec<(Gender, AnswerCount)>, Box<dyn Error>> {
+        let plays: Vec<StoredPlay> = self.answered_plays(user_id);
+        let tables = self.tables.borrow();
+        let genders: [Gender; 4] = [
+            Gender::Masculine,
+            Gender::Feminine,
+            Gender::Neuter,
+            Gender::Any,
+        ];
+        let counts: Vec<(Gender, AnswerCount)> = genders
+            .into_iter()
+            .map(|gender| {
+                let mut count = AnswerCount::default();
+                for stored in &plays {
+                    let noun: Option<&Noun> = tables
+                        .nouns
+                        .iter()
+                        .find(|noun| noun.id == stored.play.noun_id);
+                    if noun.is_some_and(|noun| noun.gender == gender) {
+                        count.answered += 1;
+                        count.correct += u32::from(stored.play.answer == Some(true));
+                    }
+                }
+                (gender, count)
+            })
+            .filter(|(_, count)| count.answered > 0)
+            .collect();
+        Ok(counts)
+    }
+
+    fn count_gender_mistakes(
+        &mut self,
+        user_id: i32,
+    ) -> Result<Vec<GenderMistake>, Box<dyn Error>> {
+        let plays: Vec<StoredPlay> = self.answered_plays(user_id);
+        let tables = self.tables.borrow();
+        let mut mistakes: Vec<GenderMistake> = Vec::new();
+        for stored in &plays {
+            let (Some(false), ExerciseType::Gender, Some(Answer::Gender(chosen))) = (
+                stored.play.answer,
+                stored.play.exercise_type,
+                &stored.play.chosen_answer,
+            ) else {
+                continue;
+            };
+            let Some(noun) = tables
+                .nouns
+                .iter()
+                .find(|noun| noun.id == stored.play.noun_id)
+            else {
+                continue;
+            };
+            match mistakes
+                .iter_mut()
+                .find(|mistake| mistake.gender == noun.gender && mistake.chosen == *chosen)
+            {
+                Some(mistake) => mistake.times += 1,
+                None => mistakes.push(GenderMistake {
+                    gender: noun.gender,
+                    chosen: *chosen,
+                    times: 1,
+                }),
+            }
+        }
+        mistakes.sort_by_key(|mistake| Reverse(mistake.times));
+        Ok(mistakes)
+    }
+
+    fn count_recent_answers_by_rank(
+        &mut self,
+        user_id: i32,
+        min_rank: u32,
+        max_rank: Option<u32>,
+        limit: u32,
+    ) -> Result<AnswerCount, Box<dyn Error>> {
+        let plays: Vec<StoredPlay> = self.answered_plays(user_id);
+        let tables = self.tables.borrow();
+        let mut count = AnswerCount::default();
+        for stored in plays.iter().rev() {
+            let noun: Option<&Noun> = tables
+                .nouns
+                .iter()
+                .find(|noun| noun.id == stored.play.noun_id);
+            if count.answered == limit {
+                break;
+            }
+            if noun.is_some_and(|noun| {
+                noun.frequency_rank > min_rank && Self::is_within(noun, max_rank)
+            }) {
+                count.answered += 1;
+                count.correct += u32::from(stored.play.answer == Some(true));
+            }
+        }
+        Ok(count)
+    }
+
+    fn count_answered_on_local_day(
+        &mut self,
+        user_id: i32,
+        utc_offset_minutes: i32,
+        day: i64,
+    ) -> Result<u32, Box<dyn Error>> {
+        let settings = UserSettings {
+            utc_offset_minutes,
+            ..UserSettings::default()
+        };
+        Ok(self
+            .answered_plays(user_id)
+            .iter()
+            .filter(|stored| settings.local_day(stored.timestamp) == day)
+            .count() as u32)
+    }
+
+    fn count_answers_by_local_day(
+        &mut self,
+        user_id: i32,
+        utc_offset_minutes: i32,
+    ) -> Result<Vec<(i64, u32)>, Box<dyn Error>> {
+        let settings = UserSettings {
+            utc_offset_minutes,
+            ..UserSettings::default()
+        };
+        let mut counts: BTreeMap<i64, u32> = BTreeMap::new();
+        for stored in self.answered_plays(user_id) {
+            *counts
+                .entry(settings.local_day(stored.timestamp))
+                .or_default() += 1;
+        }
+        Ok(counts.into_iter().collect())
+    }
+
+    fn get_mistaken_noun_ids(
+        &mut self,
+        user_id: i32,
+        required_correct: u32,
+    ) -> Result<Vec<i32>, Box<dyn Error>> {
+        let mut correct_since: HashMap<i32, u32> = HashMap::new();
+        let mut last_mistake_found: Vec<i32> = Vec::new();
+        let mut noun_ids: Vec<i32> = Vec::new();
+        for stored in self.answered_plays(user_id).iter().rev() {
+            let noun_id: i32 = stored.play.noun_id;
+            if last_mistake_found.contains(&noun_id) {
+                continue;
+            }
+            if stored.play.answer == Some(true) {
+                *correct_since.entry(noun_id).or_default() += 1;
+                continue;
+            }
+            last_mistake_found.push(noun_id);
+            if correct_since.get(&noun_id).copied().unwrap_or(0) < required_correct {
+                noun_ids.push(noun_id);
+            }
+        }
+        Ok(noun_ids)
+    }
+
+    fn get_current_streak(&mut self, user_id: i32) -> Result<u32, Box<dyn Error>> {
+        Ok(self
+            .answered_plays(user_id)
+            .iter()
+            .rev()
+            .take_while(|stored| stored.play.answer == Some(true))
+            .count() as u32)
+    }
+}
+
+impl NounReviewsRepositoryTrait for InMemoryDatabase {
+    fn get(&mut self, user_id: i32, noun_id: i32) -> Result<Option<NounReview>, Box<dyn Error>> {
+        let tables = self.tables.borrow();
+        Ok(tables
+            .reviews
+            .iter()
+            .find(|review| review.user_id == user_id && review.noun_id == noun_id)
+            .cloned())
+    }
+
+    fn upsert(&mut self, review: &NounReview) -> Result<(), Box<dyn Error>> {
+        let mut tables = self.tables.borrow_mut();
+        tables.reviews.retain(|existing| {
+            existing.user_id != review.user_id || existing.noun_id != review.noun_id
+        });
+        tables.reviews.push(review.clone());
+        Ok(())
+    }
+}
+
+impl SessionsRepositoryTrait for InMemoryDatabase {
+    fn get_open(&mut self, user_id: i32) -> Result<Option<Session>, Box<dyn Error>> {
+        let tables = self.tables.borrow();
+        Ok(tables
+            .sessions
+            .iter()
+            .rev()
+            .find(|stored| stored.session.user_id == user_id && stored.ended_at.is_none())
+            .map(|stored| stored.session.clone()))
+    }
+
+    fn insert(
+        &mut self,
+        user_id: i32,
+        first_play_id: i32,
+        started_at: SystemTime,
+    ) -> Result<i32, Box<dyn Error>> {
+        let mut tables = self.tables.borrow_mut();
+        let id: i32 = tables.sessions.len() as i32 + 1;
+        tables.sessions.push(StoredSession {
+            session: Session {
+                id,
+                user_id,
+                first_play_id,
+                last_active_at: started_at,
+            },
+            ended_at: None,
+        });
+        Ok(id)
+    }
+
+    fn touch(&mut self, session_id: i32, active_at: SystemTime) -> Result<(), Box<dyn Error>> {
+        let mut tables = self.tables.borrow_mut();
+        if let Some(stored) = tables
+            .sessions
+            .iter_mut()
+            .find(|stored| stored.session.id == session_id)
+        {
+            stored.session.last_active_at = active_at;
+        }
+        Ok(())
+    }
+
+    fn end(&mut self, session_id: i32, ended_at: SystemTime) -> Result<(), Box<dyn Error>> {
+        let mut tables = self.tables.borrow_mut();
+        if let Some(stored) = tables
+            .sessions
+            .iter_mut()
+            .find(|stored| stored.session.id == session_id)
+        {
+            stored.ended_at = Some(ended_at);
+        }
+        Ok(())
+    }
+}
+
+impl AnalyticsRepositoryTrait for InMemoryDatabase {
+    fn count_gender_confusion(
+        &mut self,
+        user_id: i32,
+        language: Language,
+    ) -> Result<Vec<GenderMistake>, Box<dyn Error>> {
+        let mut cells: Vec<GenderMistake> = Vec::new();
+        for (stored, noun) in self.answered_plays_in(user_id, language) {
+            let (ExerciseType::Gender, Some(Answer::Gender(chosen))) =
+                (stored.play.exercise_type, stored.play.chosen_answer)
+            else {
+                continue;
+            };
+            match cells
+                .iter_mut()
+                .find(|cell| cell.gender == noun.gender && cell.chosen == chosen)
+            {
+                Some(cell) => cell.times += 1,
+                None => cells.push(GenderMistake {
+                    gender: noun.gender,
+                    chosen,
+                    times: 1,
+                }),
+            }
+        }
+        Ok(cells)
+    }
+
+    fn count_answers_by_noun(
+        &mut self,
+        user_id: i32,
+        language: Language,
+    ) -> Result<Vec<NounAccuracy>, Box<dyn Error>> {
+        let mut accuracies: Vec<NounAccuracy> = Vec::new();
+        for (stored, noun) in self.answered_plays_in(user_id, language) {
+            let index: usize = match accuracies
+                .iter()
+                .position(|accuracy| accuracy.noun_id == noun.id)
+            {
+                Some(index) => index,
+                None => {
+                    accuracies.push(NounAccuracy {
+                        noun_id: noun.id,
+                        word: noun.word.clone(),
+                        count: AnswerCount::default(),
+                    });
+                    accuracies.len() - 1
+                }
+            };
+            let count: &mut AnswerCount = &mut accuracies[index].count;
+            count.answered += 1;
+            count.correct += u32::from(stored.play.answer == Some(true));
+        }
+        accuracies.sort_by_key(|accuracy| accuracy.noun_id);
+        Ok(accuracies)
+    }
+    fn refresh_noun_difficulty(&mut self, _refreshed_at: SystemTime) -> Result<(), Box<dyn Error>> {
+        let mut tables = self.tables.borrow_mut();
+        let mut totals: BTreeMap<i32, (AnswerCount, u64, u64)> = BTreeMap::new();
+        for stored in tables
+            .plays
+            .iter()
+            .filter(|stored| stored.play.answer.is_some())
+        {
+            let (count, latency_sum, latency_count) =
+                totals.entry(stored.play.noun_id).or_default();
+            count.answered += 1;
+            count.correct += u32::from(stored.play.answer == Some(true));
+            if let Some(latency_ms) = stored.play.latency_ms {
+                *latency_sum += u64::from(latency_ms);
+                *latency_count += 1;
+            }
+        }
+        tables.noun_difficulty = totals
+            .into_iter()
+            .map(
+                |(noun_id, (count, latency_sum, latency_count))| NounDifficulty {
+                    noun_id,
+                    count,
+                    mean_latency_ms: (latency_count > 0)
+                        .then(|| ((latency_sum + latency_count / 2) / latency_count) as u32),
+                },
+            )
+            .collect();
+        Ok(())
+    }
+
+    fn get_hardest_nouns(
+        &mut self,
+        min_answers: u32,
+        limit: u32,
+    ) -> Result<Vec<NounDifficulty>, Box<dyn Error>> {
+        Ok(self.ranked_difficulty(min_answers, limit, false))
+    }
+
+    fn get_easiest_nouns(
+        &mut self,
+        min_answers: u32,
+        limit: u32,
+    ) -> Result<Vec<NounDifficulty>, Box<dyn Error>> {
+        Ok(self.ranked_difficulty(min_answers, limit, true))
+    }
+}
+
+impl MistakeSessionsRepositoryTrait for InMemoryDatabase {
+    fn get(&mut self, user_id: i32) -> Result<Option<MistakesSession>, Box<dyn Error>> {
+        Ok(self.tables.borrow().mistake_sessions.get(&user_id).cloned())
+    }
+
+    fn upsert(&mut self, user_id: i32, session: &MistakesSession) -> Result<(), Box<dyn Error>> {
+        let mut tables = self.tables.borrow_mut();
+        tables.mistake_sessions.insert(user_id, session.clone());
+        Ok(())
+    }
+
+    fn remove(&mut self, user_id: i32) -> Result<(), Box<dyn Error>> {
+        self.tables.borrow_mut().mistake_sessions.remove(&user_id);
+        Ok(())
+    }
+}
@@ -0,0 +1,317 @@
+use crate::domain::{Gender, Language, Noun};
+use crate::repository::connector;
+use mysql::prelude::Queryable;
+use mysql::{params, Params, Pool, PooledConn};
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+
+pub const DEFAULT_NOUNS_CSV_PATH: &str = "nouns.csv";
+
+/// Bundled dataset of each language, Spanish being the original `nouns.csv`.
+pub fn default_path(language: Language) -> String {
+    match language {
+        Language::Spanish => DEFAULT_NOUNS_CSV_PATH.to_string(),
+        language => format!("nouns_{}.csv", language.as_str()),
+    }
+}
+
+/// Feminine Spanish nouns starting with a stressed "a", which take "el" in the
+/// singular.
+const STRESSED_A_NOUNS: [&str; 24] = [
+    "agua", "águila", "ala", "alba", "alga", "alma", "ama", "ancla", "ansia", "arca", "área",
+    "arma", "arpa", "asa", "asma", "aspa", "aula", "ave", "haba", "habla", "hacha", "hada",
+    "hambre", "hampa",
+];
+
+/// Nouns of a dataset as they would be stored, numbered from `first_id`, for
+/// playing without a database.
+pub fn read_nouns(
+    path: &str,
+    language: Language,
+    first_id: i32,
+) -> Result<(Vec<Noun>, LoadReport), Box<dyn Error>> {
+    let content: String = read(path)?;
+    let mut report = LoadReport::default();
+    let nouns: Vec<Noun> = NounsLoader::parse(&content, language, &mut report)
+        .into_iter()
+        .zip(first_id..)
+        .map(|(row, id)| Noun {
+            id,
+            english: row.english,
+            word: row.word,
+            language,
+            gender: row.gender,
+            frequency_rank: row.frequency_rank,
+            plural: row.plural,
+            stressed_a: row.stressed_a,
+        })
+        .collect();
+    report.added = nouns.len() as u32;
+    Ok((nouns, report))
+}
+
+fn read(path: &str) -> Result<String, Box<dyn Error>> {
+    fs::read_to_string(path).map_err(|e| format!("Cannot read nouns file '{}': {}", path, e).into())
+}
+
+#[derive(Debug, Clone)]
+struct NounRow {
+    line: usize,
+    english: String,
+    word: String,
+    gender: Gender,
+    frequency_rank: u32,
+    plural: Option<String>,
+    stressed_a: bool,
+}
+
+#[derive(Debug, Default)]
+pub struct LoadReport {
+    pub added: u32,
+    pub updated: u32,
+    pub unchanged: u32,
+    pub rejected: Vec<(usize, String)>,
+}
+
+impl fmt::Display for LoadReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Nouns loaded: {} added, {} updated, {} unchanged, {} rejected",
+            self.added,
+            self.updated,
+            self.unchanged,
+            self.rejected.len()
+        )?;
+        for (line, reason) in &self.rejected {
+            write!(f, "\n  line {}: {}", line, reason)?;
+        }
+        Ok(())
+    }
+}
+
+/// Loads a noun dataset from a CSV file of `english;noun;gender;plural;` rows
+/// below a header line, such as `noun_eng;noun_spa;gender;noun_spa_plural;`,
+/// upserting each noun by its language and form. The plural is optional and
+/// only needed when the regular rules don't produce it. The file lists the
+/// most frequent nouns first, so the order of the rows gives their rank
+/// within the language.
+pub struct NounsLoader {
+    pool: Pool,
+}
+
+impl NounsLoader {
+    pub fn new(pool: Pool) -> Self {
+        NounsLoader { pool }
+    }
+
+    pub fn load(&mut self, path: &str, language: Language) -> Result<LoadReport, Box<dyn Error>> {
+        let content: String = read(path)?;
+        let mut report = LoadReport::default();
+        let mut conn: PooledConn = connector::get_conn(&self.pool)?;
+
+        for row in Self::parse(&content, language, &mut report) {
+            match Self::upsert(&mut conn, &row, language)? {
+                1 => report.added += 1,
+                2 => report.updated += 1,
+                _ => report.unchanged += 1,
+            }
+        }
+        Ok(report)
+    }
+
+    fn parse(content: &str, language: Language, report: &mut LoadReport) -> Vec<NounRow> {
+        let mut first_seen: HashMap<String, usize> = HashMap::new();
+        let mut rows: Vec<NounRow> = Vec::new();
+
+        for (index, raw_line) in content.lines().enumerate().skip(1) {
+            let line: usize = index + 1;
+            if raw_line.trim().is_empty() {
+                continue;
+            }
+            let row: NounRow = match Self::parse_line(line, raw_line, language) {
+                Ok(row) => row,
+                Err(reason) => {
+                    report.rejected.push((line, reason));
+                    continue;
+                }
+            };
+            if let Some(first_line) = first_seen.get(&row.word) {
+                let reason = format!("'{}' already defined on line {}", row.word, first_line);
+                report.rejected.push((line, reason));
+                continue;
+            }
+            first_seen.insert(row.word.clone(), line);
+            rows.push(NounRow {
+                frequency_rank: rows.len() as u32 + 1,
+                ..row
+            });
+        }
+        rows
+    }
+
+    fn parse_line(line: usize, raw_line: &str, language: Language) -> Result<NounRow, String> {
+        let fields: Vec<&str> = raw_line.split(';').map(str::trim).collect();
+        let (english, word, gender_str, plural) = match fields.as_slice() {
+            [english, word, gender] => (*english, *word, *gender, ""),
+            [english, word, gender, plural] | [english, word, gender, plural, ""] => {
+                (*english, *word, *gender, *plural)
+            }
+            _ => return Err(format!("expected 3 or 4 fields, found {}", fields.len())),
+        };
+        if english.is_empty() || word.is_empty() {
+            return Err("empty noun".to_string());
+        }
+        let gender: Gender = gender_str.to_lowercase().parse()?;
+        if !language.answer_genders().contains(&gender) {
+            return Err(format!("{} nouns can't be {}", language, gender.as_str()));
+        }
+
+        Ok(NounRow {
+            line,
+            english: english.to_string(),
+            word: word.to_string(),
+            gender,
+            frequency_rank: 0,
+            plural: Some(plural.to_string()).filter(|plural| !plural.is_empty()),
+            stressed_a: language == Language::Spanish
+                && gender == Gender::Feminine
+                && STRESSED_A_NOUNS.contains(&word),
+        })
+    }
+
+    /// Returns the affected row count: 1 when inserted, 2 when updated and 0
+    /// when the stored noun was already identical.
+    fn upsert(
+        conn: &mut PooledConn,
+        row: &NounRow,
+        language: Language,
+    ) -> Result<u64, Box<dyn Error>> {
+        let statement: &str = "\
+            INSERT INTO nouns \
+                (english, word, language, gender, frequency_rank, plural, stressed_a) \
+            VALUES \
+                (:english, :word, :language, :gender, :frequency_rank, :plural, :stressed_a) \
+            ON DUPLICATE KEY UPDATE \
+                english = VALUES(english), \
+                gender = VALUES(gender), \
+                frequency_rank = VALUES(frequency_rank), \
+                plural = VALUES(plural), \
+                stressed_a = VALUES(stressed_a)";
+        let params: Params = params! {
+            "english" => &row.english,
+            "word" => &row.word,
+            "language" => language.as_str(),
+            "gender" => row.gender.as_str(),
+            "frequency_rank" => row.frequency_rank,
+            "plural" => &row.plural,
+            "stressed_a" => row.stressed_a,
+        };
+        conn.exec_drop(statement, params)
+            .map_err(|e| format!("line {}: {}", row.line, e).into())
+            .map(|_| conn.affected_rows())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HEADER: &str = "noun_eng;noun_spa;gender;noun_spa_plural;";
+
+    fn parse(rows: &[&str], language: Language) -> (Vec<NounRow>, LoadReport) {
+        let content: String = [HEADER]
+            .iter()
+            .chain(rows)
+            .copied()
+            .collect::<Vec<_>>()
+            .join("\n");
+        let mut report = LoadReport::default();
+        let parsed: Vec<NounRow> = NounsLoader::parse(&content, language, &mut report);
+        (parsed, report)
+    }
+
+    #[test]
+    fn valid_rows_are_ranked_in_file_order() {
+        let (rows, report) = parse(
+            &[
+                "time;tiempo;masculine;",
+                "",
+                "image;imagen;feminine;imágenes;",
+            ],
+            Language::Spanish,
+        );
+
+        assert!(report.rejected.is_empty());
+        let ranked: Vec<(&str, u32, Option<&str>)> = rows
+            .iter()
+            .map(|row| (row.word.as_str(), row.frequency_rank, row.plural.as_deref()))
+            .collect();
+        assert_eq!(
+            ranked,
+            vec![("tiempo", 1, None), ("imagen", 2, Some("imágenes"))]
+        );
+        assert_eq!(rows[1].line, 4);
+    }
+
+    #[test]
+    fn malformed_rows_are_rejected_with_their_line() {
+        let (rows, report) = parse(
+            &[
+                "time;tiempo",
+                "house;casa;feminine;casas;extra;more",
+                ";mesa;feminine;",
+                "man;;masculine;",
+                "life;vida;femenino;",
+            ],
+            Language::Spanish,
+        );
+
+        assert!(rows.is_empty());
+        assert_eq!(
+            report.rejected,
+            vec![
+                (2, "expected 3 or 4 fields, found 2".to_string()),
+                (3, "expected 3 or 4 fields, found 6".to_string()),
+                (4, "empty noun".to_string()),
+                (5, "empty noun".to_string()),
+                (6, "Invalid gender 'femenino'".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn genders_the_language_lacks_are_rejected() {
+        let (rows, report) = parse(&["girl;chica;neuter;"], Language::Spanish);
+
+        assert!(rows.is_empty());
+        assert_eq!(
+            report.rejected,
+            vec![(2, "Spanish nouns can't be neuter".to_string())]
+        );
+    }
+
+    #[test]
+    fn duplicates_are_rejected_without_taking_a_rank() {
+        let (rows, report) = parse(
+            &[
+                "time;tiempo;masculine;",
+                "weather;tiempo;masculine;",
+                "house;casa;feminine;",
+            ],
+            Language::Spanish,
+        );
+
+        let ranked: Vec<(&str, u32)> = rows
+            .iter()
+            .map(|row| (row.word.as_str(), row.frequency_rank))
+            .collect();
+        assert_eq!(ranked, vec![("tiempo", 1), ("casa", 2)]);
+        assert_eq!(
+            report.rejected,
+            vec![(3, "'tiempo' already defined on line 2".to_string())]
+        );
+    }
+}
@@ -1,13 +1,13 @@
 use crate::repository::connector;
-use mysql::PooledConn;
+use mysql::{Pool, PooledConn};
 
 mod embedded {
     use refinery::embed_migrations;
     embed_migrations!("migrations");
 }
 
-pub fn run() -> Result<(), Box<dyn std::error::Error>> {
-    let mut conn: PooledConn = connector::new()?;
+pub fn run(pool: &Pool) -> Result<(), Box<dyn std::error::Error>> {
+    let mut conn: PooledConn = connector::get_conn(pool)?;
     embedded::migrations::runner().run(&mut conn)?;
     println!("Migrations applied successfully!");
     Ok(())
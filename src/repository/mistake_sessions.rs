@@ -0,0 +1,83 @@
+use crate::domain::{AnswerCount, MistakesSession};
+use crate::repository::connector;
+use mysql::prelude::Queryable;
+use mysql::{params, Params, Pool, PooledConn};
+use std::error::Error;
+
+/// Reviews under way, one per user, so a restart doesn't interrupt them. The
+/// queue and the nouns still missed are stored as JSON arrays.
+pub struct MistakeSessionsRepository {
+    pool: Pool,
+}
+
+impl MistakeSessionsRepository {
+    pub fn new(pool: Pool) -> Self {
+        MistakeSessionsRepository { pool }
+    }
+
+    fn conn(&self) -> Result<PooledConn, Box<dyn Error>> {
+        connector::get_conn(&self.pool)
+    }
+
+    fn build(
+        result: Option<(String, u32, u32, String)>,
+    ) -> Result<Option<MistakesSession>, Box<dyn Error>> {
+        let Some((queue, answered, correct, still_missed)) = result else {
+            return Ok(None);
+        };
+        Ok(Some(MistakesSession {
+            queue: serde_json::from_str(&queue)?,
+            count: AnswerCount { answered, correct },
+            still_missed: serde_json::from_str(&still_missed)?,
+        }))
+    }
+}
+
+pub trait MistakeSessionsRepositoryTrait {
+    fn get(&mut self, user_id: i32) -> Result<Option<MistakesSession>, Box<dyn Error>>;
+    fn upsert(&mut self, user_id: i32, session: &MistakesSession) -> Result<(), Box<dyn Error>>;
+    fn remove(&mut self, user_id: i32) -> Result<(), Box<dyn Error>>;
+}
+
+impl MistakeSessionsRepositoryTrait for MistakeSessionsRepository {
+    fn get(&mut self, user_id: i32) -> Result<Option<MistakesSession>, Box<dyn Error>> {
+        let statement: &str = "\
+            SELECT queue, answered, correct, still_missed \
+            FROM mistake_sessions \
+            WHERE user_id = :user_id";
+        let params: Params = params! {"user_id" => user_id};
+        self.conn()?
+            .exec_first(statement, params)
+            .map_err(|e| e.into())
+            .and_then(Self::build)
+    }
+
+    fn upsert(&mut self, user_id: i32, session: &MistakesSession) -> Result<(), Box<dyn Error>> {
+        let statement: &str = "\
+            INSERT INTO mistake_sessions (user_id, queue, answered, correct, still_missed) \
+            VALUES (:user_id, :queue, :answered, :correct, :still_missed) \
+            ON DUPLICATE KEY UPDATE \
+                queue = VALUES(queue), \
+                answered = VALUES(answered), \
+                correct = VALUES(correct), \
+                still_missed = VALUES(still_missed)";
+        let params: Params = params! {
+            "user_id" => user_id,
+            "queue" => serde_json::to_string(&session.queue)?,
+            "answered" => session.count.answered,
+            "correct" => session.count.correct,
+            "still_missed" => serde_json::to_string(&session.still_missed)?,
+        };
+        self.conn()?
+            .exec_drop(statement, params)
+            .map_err(|e| e.into())
+    }
+
+    fn remove(&mut self, user_id: i32) -> Result<(), Box<dyn Error>> {
+        let statement: &str = "DELETE FROM mistake_sessions WHERE user_id = :user_id";
+        let params: Params = params! {"user_id" => user_id};
+        self.conn()?
+            .exec_drop(statement, params)
+            .map_err(|e| e.into())
+    }
+}
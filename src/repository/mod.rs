@@ -1,6 +1,49 @@
+pub mod analytics;
+pub mod mistake_sessions;
+pub mod noun_reviews;
 pub mod nouns;
+pub mod sessions;
 pub mod user_plays;
 pub mod users;
 
 pub mod connector;
+pub mod in_memory;
+pub mod loader;
 pub mod migration;
+
+use crate::repository::analytics::{AnalyticsRepository, AnalyticsRepositoryTrait};
+use crate::repository::mistake_sessions::{
+    MistakeSessionsRepository, MistakeSessionsRepositoryTrait,
+};
+use crate::repository::noun_reviews::{NounReviewsRepository, NounReviewsRepositoryTrait};
+use crate::repository::nouns::{NounsRepository, NounsRepositoryTrait};
+use crate::repository::sessions::{SessionsRepository, SessionsRepositoryTrait};
+use crate::repository::user_plays::{UserPlaysRepository, UserPlaysRepositoryTrait};
+use crate::repository::users::{UsersRepository, UsersRepositoryTrait};
+use mysql::Pool;
+
+/// Repositories a service works with, behind their traits so the storage can
+/// be swapped (e.g. for [`in_memory::InMemoryDatabase`] in tests).
+pub struct Repositories {
+    pub users: Box<dyn UsersRepositoryTrait>,
+    pub nouns: Box<dyn NounsRepositoryTrait>,
+    pub user_plays: Box<dyn UserPlaysRepositoryTrait>,
+    pub noun_reviews: Box<dyn NounReviewsRepositoryTrait>,
+    pub sessions: Box<dyn SessionsRepositoryTrait>,
+    pub analytics: Box<dyn AnalyticsRepositoryTrait>,
+    pub mistake_sessions: Box<dyn MistakeSessionsRepositoryTrait>,
+}
+
+impl Repositories {
+    pub fn new(pool: Pool) -> Self {
+        Self {
+            users: Box::new(UsersRepository::new(pool.clone())),
+            nouns: Box::new(NounsRepository::new(pool.clone())),
+            user_plays: Box::new(UserPlaysRepository::new(pool.clone())),
+            noun_reviews: Box::new(NounReviewsRepository::new(pool.clone())),
+            sessions: Box::new(SessionsRepository::new(pool.clone())),
+            analytics: Box::new(AnalyticsRepository::new(pool.clone())),
+            mistake_sessions: Box::new(MistakeSessionsRepository::new(pool)),
+        }
+    }
+}
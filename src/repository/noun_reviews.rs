@@ -0,0 +1,83 @@
+use crate::domain::NounReview;
+use crate::repository::connector;
+use mysql::prelude::Queryable;
+use mysql::{params, Params, Pool, PooledConn};
+use std::error::Error;
+use std::time::{Duration, UNIX_EPOCH};
+
+/// The due time is read as unix seconds.
+type NounReviewRow = (i32, i32, u32, u32, f64, u64);
+
+pub struct NounReviewsRepository {
+    pool: Pool,
+}
+
+impl NounReviewsRepository {
+    pub fn new(pool: Pool) -> Self {
+        NounReviewsRepository { pool }
+    }
+
+    fn conn(&self) -> Result<PooledConn, Box<dyn Error>> {
+        connector::get_conn(&self.pool)
+    }
+
+    fn build(result: Option<NounReviewRow>) -> Option<NounReview> {
+        result.map(
+            |(user_id, noun_id, repetitions, interval_minutes, ease_factor, due_at)| NounReview {
+                user_id,
+                noun_id,
+                repetitions,
+                interval_minutes,
+                ease_factor,
+                due_at: UNIX_EPOCH + Duration::from_secs(due_at),
+            },
+        )
+    }
+}
+
+pub trait NounReviewsRepositoryTrait {
+    fn get(&mut self, user_id: i32, noun_id: i32) -> Result<Option<NounReview>, Box<dyn Error>>;
+    fn upsert(&mut self, review: &NounReview) -> Result<(), Box<dyn Error>>;
+}
+
+impl NounReviewsRepositoryTrait for NounReviewsRepository {
+    fn get(&mut self, user_id: i32, noun_id: i32) -> Result<Option<NounReview>, Box<dyn Error>> {
+        let statement: &str = "\
+            SELECT user_id, noun_id, repetitions, interval_minutes, ease_factor, \
+                UNIX_TIMESTAMP(due_at) \
+            FROM noun_reviews \
+            WHERE user_id = :user_id AND noun_id = :noun_id";
+        let params: Params = params! {
+            "user_id" => user_id,
+            "noun_id" => noun_id,
+        };
+        self.conn()?
+            .exec_first(statement, params)
+            .map(Self::build)
+            .map_err(|e| e.into())
+    }
+
+    fn upsert(&mut self, review: &NounReview) -> Result<(), Box<dyn Error>> {
+        let statement: &str = "\
+            INSERT INTO noun_reviews \
+                (user_id, noun_id, repetitions, interval_minutes, ease_factor, due_at) \
+            VALUES (:user_id, :noun_id, :repetitions, :interval_minutes, :ease_factor, \
+                FROM_UNIXTIME(:due_at)) \
+            ON DUPLICATE KEY UPDATE \
+                repetitions = VALUES(repetitions), \
+                interval_minutes = VALUES(interval_minutes), \
+                ease_factor = VALUES(ease_factor), \
+                due_at = VALUES(due_at)";
+        let params: Params = params! {
+            "user_id" => review.user_id,
+            "noun_id" => review.noun_id,
+            "repetitions" => review.repetitions,
+            "interval_minutes" => review.interval_minutes,
+            "ease_factor" => review.ease_factor,
+            "due_at" => review.due_at.duration_since(UNIX_EPOCH)?.as_secs(),
+        };
+        self.conn()?
+            .exec_drop(statement, params)
+            .map_err(|e| e.into())
+    }
+}
@@ -1,55 +1,164 @@
-use crate::domain::{Gender, Noun};
+use crate::domain::{Gender, Language, Noun};
 use crate::repository::connector;
 use mysql::prelude::Queryable;
-use mysql::{params, Params, PooledConn};
+use mysql::{params, Params, Pool, PooledConn};
 use std::error::Error;
 
+type NounRow = (
+    i32,
+    String,
+    String,
+    String,
+    String,
+    u32,
+    Option<String>,
+    bool,
+);
+
 pub struct NounsRepository {
-    conn: PooledConn,
+    pool: Pool,
 }
 
 impl NounsRepository {
-    pub fn new() -> Self {
-        let conn: PooledConn = connector::new().unwrap();
-        NounsRepository { conn }
+    pub fn new(pool: Pool) -> Self {
+        NounsRepository { pool }
+    }
+
+    fn conn(&self) -> Result<PooledConn, Box<dyn Error>> {
+        connector::get_conn(&self.pool)
     }
 
-    fn build(result: Option<(i32, String, String, String)>) -> Result<Noun, Box<dyn Error>> {
-        let (id, english, spanish, gender_str) = result.ok_or("No nouns found in the database")?;
+    fn build(result: Option<NounRow>) -> Result<Noun, Box<dyn Error>> {
+        let (id, english, word, language_str, gender_str, frequency_rank, plural, stressed_a) =
+            result.ok_or("No nouns found in the database")?;
 
-        let gender: Gender = match gender_str.as_str() {
-            "masculine" => Gender::Masculine,
-            "feminine" => Gender::Feminine,
-            "any" => Gender::Any,
-            _ => return Err(format!("Invalid gender '{}' in database", gender_str).into()),
-        };
-        Ok(Noun {id, english, spanish, gender})
+        let language: Language = language_str.parse()?;
+        let gender: Gender = gender_str.parse()?;
+        Ok(Noun {
+            id,
+            english,
+            word,
+            language,
+            gender,
+            frequency_rank,
+            plural,
+            stressed_a,
+        })
+    }
+
+    fn build_optional(result: Option<NounRow>) -> Result<Option<Noun>, Box<dyn Error>> {
+        result.map(|row| Self::build(Some(row))).transpose()
     }
 }
 
 pub trait NounsRepositoryTrait {
     fn get(&mut self, id: i32) -> Result<Noun, Box<dyn Error>>;
-    fn get_random(&mut self) -> Result<Noun, Box<dyn Error>>;
+    /// Picks any noun of the language ranked up to `max_rank`, or any noun of
+    /// the language at all without it.
+    fn get_random(
+        &mut self,
+        language: Language,
+        max_rank: Option<u32>,
+    ) -> Result<Noun, Box<dyn Error>>;
+    fn get_most_overdue(
+        &mut self,
+        user_id: i32,
+        language: Language,
+    ) -> Result<Option<Noun>, Box<dyn Error>>;
+    /// Picks a noun of the language ranked up to `max_rank` that the user has
+    /// neither been reviewed on nor skipped.
+    fn get_random_unseen(
+        &mut self,
+        user_id: i32,
+        language: Language,
+        max_rank: Option<u32>,
+    ) -> Result<Option<Noun>, Box<dyn Error>>;
 }
 
 impl NounsRepositoryTrait for NounsRepository {
     fn get(&mut self, id: i32) -> Result<Noun, Box<dyn Error>> {
-        let statement: &str = "SELECT id, english, spanish, gender FROM nouns WHERE id = :id";
+        let statement: &str = "\
+            SELECT id, english, word, language, gender, frequency_rank, plural, stressed_a \
+            FROM nouns \
+            WHERE id = :id";
         let params: Params = params! {"id" => id};
-        self.conn
+        self.conn()?
             .exec_first(statement, params)
             .map_err(|e| e.into())
             .and_then(Self::build)
     }
-    fn get_random(&mut self) -> Result<Noun, Box<dyn Error>> {
+
+    fn get_random(
+        &mut self,
+        language: Language,
+        max_rank: Option<u32>,
+    ) -> Result<Noun, Box<dyn Error>> {
         let statement: &str = "\
-            SELECT id, english, spanish, gender \
+            SELECT id, english, word, language, gender, frequency_rank, plural, stressed_a \
             FROM nouns \
+            WHERE language = :language AND (:max_rank IS NULL OR frequency_rank <= :max_rank) \
             ORDER BY RAND() \
             LIMIT 1";
-        self.conn
-            .query_first(statement)
+        let params: Params = params! {
+            "language" => language.as_str(),
+            "max_rank" => max_rank,
+        };
+        self.conn()?
+            .exec_first(statement, params)
             .map_err(|e| e.into())
             .and_then(Self::build)
     }
+
+    fn get_most_overdue(
+        &mut self,
+        user_id: i32,
+        language: Language,
+    ) -> Result<Option<Noun>, Box<dyn Error>> {
+        let statement: &str = "\
+            SELECT n.id, n.english, n.word, n.language, n.gender, n.frequency_rank, n.plural, \
+                n.stressed_a \
+            FROM nouns n \
+            JOIN noun_reviews r ON r.noun_id = n.id \
+            WHERE r.user_id = :user_id AND n.language = :language AND r.due_at <= NOW() \
+            ORDER BY r.due_at \
+            LIMIT 1";
+        let params: Params = params! {
+            "user_id" => user_id,
+            "language" => language.as_str(),
+        };
+        self.conn()?
+            .exec_first(statement, params)
+            .map_err(|e| e.into())
+            .and_then(Self::build_optional)
+    }
+
+    fn get_random_unseen(
+        &mut self,
+        user_id: i32,
+        language: Language,
+        max_rank: Option<u32>,
+    ) -> Result<Option<Noun>, Box<dyn Error>> {
+        let statement: &str = "\
+            SELECT n.id, n.english, n.word, n.language, n.gender, n.frequency_rank, n.plural, \
+                n.stressed_a \
+            FROM nouns n \
+            LEFT JOIN noun_reviews r ON r.noun_id = n.id AND r.user_id = :user_id \
+            WHERE r.noun_id IS NULL AND n.language = :language \
+                AND (:max_rank IS NULL OR n.frequency_rank <= :max_rank) \
+                AND NOT EXISTS ( \
+                    SELECT 1 FROM user_plays p \
+                    WHERE p.user_id = :user_id AND p.noun_id = n.id AND p.outcome = 'skipped' \
+                ) \
+            ORDER BY RAND() \
+            LIMIT 1";
+        let params: Params = params! {
+            "user_id" => user_id,
+            "language" => language.as_str(),
+            "max_rank" => max_rank,
+        };
+        self.conn()?
+            .exec_first(statement, params)
+            .map_err(|e| e.into())
+            .and_then(Self::build_optional)
+    }
 }
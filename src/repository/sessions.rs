@@ -0,0 +1,109 @@
+use crate::domain::Session;
+use crate::repository::connector;
+use mysql::prelude::Queryable;
+use mysql::{params, Params, Pool, PooledConn};
+use std::error::Error;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+pub struct SessionsRepository {
+    pool: Pool,
+}
+
+impl SessionsRepository {
+    pub fn new(pool: Pool) -> Self {
+        SessionsRepository { pool }
+    }
+
+    fn conn(&self) -> Result<PooledConn, Box<dyn Error>> {
+        connector::get_conn(&self.pool)
+    }
+
+    fn build(result: Option<(i32, i32, i32, u64)>) -> Option<Session> {
+        result.map(|(id, user_id, first_play_id, last_active_at)| Session {
+            id,
+            user_id,
+            first_play_id,
+            last_active_at: UNIX_EPOCH + Duration::from_secs(last_active_at),
+        })
+    }
+
+    fn unix_seconds(time: SystemTime) -> Result<u64, Box<dyn Error>> {
+        Ok(time.duration_since(UNIX_EPOCH)?.as_secs())
+    }
+}
+
+pub trait SessionsRepositoryTrait {
+    fn get_open(&mut self, user_id: i32) -> Result<Option<Session>, Box<dyn Error>>;
+    fn insert(
+        &mut self,
+        user_id: i32,
+        first_play_id: i32,
+        started_at: SystemTime,
+    ) -> Result<i32, Box<dyn Error>>;
+    fn touch(&mut self, session_id: i32, active_at: SystemTime) -> Result<(), Box<dyn Error>>;
+    fn end(&mut self, session_id: i32, ended_at: SystemTime) -> Result<(), Box<dyn Error>>;
+}
+
+impl SessionsRepositoryTrait for SessionsRepository {
+    fn get_open(&mut self, user_id: i32) -> Result<Option<Session>, Box<dyn Error>> {
+        let statement: &str = "\
+            SELECT id, user_id, first_play_id, UNIX_TIMESTAMP(last_active_at) \
+            FROM sessions \
+            WHERE user_id = :user_id AND ended_at IS NULL \
+            ORDER BY id DESC \
+            LIMIT 1";
+        let params: Params = params! {"user_id" => user_id};
+        self.conn()?
+            .exec_first(statement, params)
+            .map(Self::build)
+            .map_err(|e| e.into())
+    }
+
+    fn insert(
+        &mut self,
+        user_id: i32,
+        first_play_id: i32,
+        started_at: SystemTime,
+    ) -> Result<i32, Box<dyn Error>> {
+        let statement: &str = "\
+            INSERT INTO sessions (user_id, first_play_id, started_at, last_active_at) \
+            VALUES (:user_id, :first_play_id, FROM_UNIXTIME(:started_at), \
+                FROM_UNIXTIME(:started_at))";
+        let params: Params = params! {
+            "user_id" => user_id,
+            "first_play_id" => first_play_id,
+            "started_at" => Self::unix_seconds(started_at)?,
+        };
+        let mut conn: PooledConn = self.conn()?;
+        conn.exec_drop(statement, params)?;
+        Ok(conn.last_insert_id() as i32)
+    }
+
+    fn touch(&mut self, session_id: i32, active_at: SystemTime) -> Result<(), Box<dyn Error>> {
+        let statement: &str = "\
+            UPDATE sessions \
+            SET last_active_at = FROM_UNIXTIME(:active_at) \
+            WHERE id = :id";
+        let params: Params = params! {
+            "id" => session_id,
+            "active_at" => Self::unix_seconds(active_at)?,
+        };
+        self.conn()?
+            .exec_drop(statement, params)
+            .map_err(|e| e.into())
+    }
+
+    fn end(&mut self, session_id: i32, ended_at: SystemTime) -> Result<(), Box<dyn Error>> {
+        let statement: &str = "\
+            UPDATE sessions \
+            SET ended_at = FROM_UNIXTIME(:ended_at) \
+            WHERE id = :id";
+        let params: Params = params! {
+            "id" => session_id,
+            "ended_at" => Self::unix_seconds(ended_at)?,
+        };
+        self.conn()?
+            .exec_drop(statement, params)
+            .map_err(|e| e.into())
+    }
+}
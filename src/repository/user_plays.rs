@@ -1,76 +1,427 @@
-use crate::domain::UserPlay;
+use crate::domain::{
+    Answer, AnswerCount, ExerciseType, Gender, GenderMistake, PlayOutcome, PlayResponse, UserPlay,
+};
 use crate::repository::connector;
 use mysql::prelude::Queryable;
-use mysql::{params, Params, PooledConn};
+use mysql::{params, Params, Pool, PooledConn};
 use std::error::Error;
 
+type UserPlayRow = (
+    i32,
+    i32,
+    i32,
+    String,
+    Option<bool>,
+    Option<String>,
+    Option<String>,
+    Option<String>,
+    Option<u32>,
+);
+
+/// Chosen answer of the plays ended without one, or answered before answers
+/// were recorded.
+pub const UNKNOWN_ANSWER: &str = "unknown";
+
 pub struct UserPlaysRepository {
-    conn: PooledConn,
+    pool: Pool,
 }
 
 impl UserPlaysRepository {
-    pub fn new() -> Self {
-        let conn: PooledConn = connector::new().unwrap();
-        UserPlaysRepository { conn }
+    pub fn new(pool: Pool) -> Self {
+        UserPlaysRepository { pool }
+    }
+
+    fn conn(&self) -> Result<PooledConn, Box<dyn Error>> {
+        connector::get_conn(&self.pool)
     }
 
-    fn build(result: Option<(i32, i32, i32, Option<bool>)>) -> Option<UserPlay> {
-        result.map(|(id, user_id, noun_id, answer)| UserPlay {
+    fn build(result: Option<UserPlayRow>) -> Result<Option<UserPlay>, Box<dyn Error>> {
+        let Some((
             id,
             user_id,
             noun_id,
+            exercise_type_str,
             answer,
-        })
+            outcome_str,
+            chosen_answer_str,
+            answer_text,
+            latency_ms,
+        )) = result
+        else {
+            return Ok(None);
+        };
+        let exercise_type: ExerciseType = exercise_type_str.parse()?;
+        let outcome: Option<PlayOutcome> =
+            outcome_str.map(|outcome| outcome.parse()).transpose()?;
+        let chosen_answer: Option<Answer> = chosen_answer_str
+            .filter(|chosen| chosen != UNKNOWN_ANSWER)
+            .map(|chosen| {
+                Answer::parse(exercise_type, &chosen)
+                    .ok_or_else(|| format!("Invalid chosen answer '{}'", chosen))
+            })
+            .transpose()?;
+        Ok(Some(UserPlay {
+            id,
+            user_id,
+            noun_id,
+            exercise_type,
+            answer,
+            outcome,
+            chosen_answer,
+            answer_text,
+            latency_ms,
+        }))
+    }
+
+    fn build_count(result: Option<(u32, u32)>) -> AnswerCount {
+        result
+            .map(|(answered, correct)| AnswerCount { answered, correct })
+            .unwrap_or_default()
     }
 }
 
 pub trait UserPlaysRepositoryTrait {
+    fn get(&mut self, play_id: i32) -> Result<Option<UserPlay>, Box<dyn Error>>;
     fn get_last(&mut self, user_id: i32) -> Result<Option<UserPlay>, Box<dyn Error>>;
-    fn insert(&mut self, user_id: i32, noun_id: i32) -> Result<i32, Box<dyn Error>>;
-    fn update(&mut self, play_id: i32, answer: bool) -> Result<(), Box<dyn Error>>;
-    fn remove(&mut self, play_id: i32) -> Result<(), Box<dyn Error>>;
+    fn get_last_answered(&mut self, user_id: i32) -> Result<Option<UserPlay>, Box<dyn Error>>;
+    fn insert(
+        &mut self,
+        user_id: i32,
+        noun_id: i32,
+        exercise_type: ExerciseType,
+    ) -> Result<i32, Box<dyn Error>>;
+    /// Ends the play, timing the response from when it was inserted.
+    fn update(&mut self, play_id: i32, response: &PlayResponse) -> Result<(), Box<dyn Error>>;
+    fn count_answers(&mut self, user_id: i32) -> Result<AnswerCount, Box<dyn Error>>;
+    /// Plays answered from `first_play_id` on, oldest first.
+    fn get_answered_since(
+        &mut self,
+        user_id: i32,
+        first_play_id: i32,
+    ) -> Result<Vec<UserPlay>, Box<dyn Error>>;
+    fn count_answers_by_gender(
+        &mut self,
+        user_id: i32,
+    ) -> Result<Vec<(Gender, AnswerCount)>, Box<dyn Error>>;
+    /// Wrong genders picked on gender questions, most frequent first.
+    fn count_gender_mistakes(&mut self, user_id: i32)
+        -> Result<Vec<GenderMistake>, Box<dyn Error>>;
+    /// Counts the latest `limit` answers on nouns ranked above `min_rank` and
+    /// up to `max_rank`.
+    fn count_recent_answers_by_rank(
+        &mut self,
+        user_id: i32,
+        min_rank: u32,
+        max_rank: Option<u32>,
+        limit: u32,
+    ) -> Result<AnswerCount, Box<dyn Error>>;
+    /// Answers given on the day, as days since the Unix epoch at the given UTC
+    /// offset, so it matches the days of [`Self::count_answers_by_local_day`].
+    fn count_answered_on_local_day(
+        &mut self,
+        user_id: i32,
+        utc_offset_minutes: i32,
+        day: i64,
+    ) -> Result<u32, Box<dyn Error>>;
+    /// Answers given on each day, as days since the Unix epoch at the given
+    /// UTC offset, oldest first. Days without answers are left out.
+    fn count_answers_by_local_day(
+        &mut self,
+        user_id: i32,
+        utc_offset_minutes: i32,
+    ) -> Result<Vec<(i64, u32)>, Box<dyn Error>>;
+    /// Nouns answered wrong and answered right fewer than `required_correct`
+    /// times since, latest mistakes first.
+    fn get_mistaken_noun_ids(
+        &mut self,
+        user_id: i32,
+        required_correct: u32,
+    ) -> Result<Vec<i32>, Box<dyn Error>>;
+    fn get_current_streak(&mut self, user_id: i32) -> Result<u32, Box<dyn Error>>;
 }
 
 impl UserPlaysRepositoryTrait for UserPlaysRepository {
+    fn get(&mut self, play_id: i32) -> Result<Option<UserPlay>, Box<dyn Error>> {
+        let statement: &str = "\
+            SELECT id, user_id, noun_id, exercise_type, answer, outcome, \
+                chosen_answer, answer_text, latency_ms \
+            FROM user_plays \
+            WHERE id = :id";
+        let params: Params = params! {"id" => play_id};
+        self.conn()?
+            .exec_first(statement, params)
+            .map_err(|e| e.into())
+            .and_then(Self::build)
+    }
     fn get_last(&mut self, user_id: i32) -> Result<Option<UserPlay>, Box<dyn Error>> {
         let statement: &str = "\
-            SELECT id, user_id, noun_id, answer \
+            SELECT id, user_id, noun_id, exercise_type, answer, outcome, \
+                chosen_answer, answer_text, latency_ms \
             FROM user_plays \
-            WHERE user_id = :user_id AND answer IS NULL \
+            WHERE user_id = :user_id AND outcome IS NULL \
             ORDER BY timestamp DESC \
             LIMIT 1";
         let params = params! { "user_id" => user_id };
-        self.conn
+        self.conn()?
+            .exec_first(statement, params)
+            .map_err(|e| e.into())
+            .and_then(Self::build)
+    }
+    fn get_last_answered(&mut self, user_id: i32) -> Result<Option<UserPlay>, Box<dyn Error>> {
+        let statement: &str = "\
+            SELECT id, user_id, noun_id, exercise_type, answer, outcome, \
+                chosen_answer, answer_text, latency_ms \
+            FROM user_plays \
+            WHERE user_id = :user_id AND answer IS NOT NULL \
+            ORDER BY id DESC \
+            LIMIT 1";
+        let params = params! { "user_id" => user_id };
+        self.conn()?
             .exec_first(statement, params)
-            .map(Self::build)
             .map_err(|e| e.into())
+            .and_then(Self::build)
     }
-    fn insert(&mut self, user_id: i32, noun_id: i32) -> Result<i32, Box<dyn Error>> {
+    fn insert(
+        &mut self,
+        user_id: i32,
+        noun_id: i32,
+        exercise_type: ExerciseType,
+    ) -> Result<i32, Box<dyn Error>> {
         let statement: &str = "\
-            INSERT INTO user_plays (user_id, noun_id) \
-            VALUES (:user_id, :noun_id)";
+            INSERT INTO user_plays (user_id, noun_id, exercise_type) \
+            VALUES (:user_id, :noun_id, :exercise_type)";
         let params = params! {
             "user_id" => user_id,
             "noun_id" => noun_id,
+            "exercise_type" => exercise_type.as_str(),
         };
-        self.conn
+        let mut conn: PooledConn = self.conn()?;
+        conn.exec_drop(statement, params)?;
+        Ok(conn.last_insert_id() as i32)
+    }
+
+    fn update(&mut self, play_id: i32, response: &PlayResponse) -> Result<(), Box<dyn Error>> {
+        let statement: &str = "\
+            UPDATE user_plays \
+            SET answer = :answer, outcome = :outcome, chosen_answer = :chosen_answer, \
+                answer_text = :answer_text, \
+                latency_ms = GREATEST(TIMESTAMPDIFF(MICROSECOND, timestamp, NOW(3)) DIV 1000, 0) \
+            WHERE id = :id";
+        let chosen_answer: &str = response
+            .chosen_answer
+            .as_ref()
+            .map_or(UNKNOWN_ANSWER, Answer::as_str);
+        let params: Params = params! {
+            "id" => play_id,
+            "answer" => response.outcome.answer(),
+            "outcome" => response.outcome.as_str(),
+            "chosen_answer" => chosen_answer,
+            "answer_text" => &response.answer_text,
+        };
+        self.conn()?
             .exec_drop(statement, params)
             .map_err(|e| e.into())
-            .map(|_| self.conn.last_insert_id() as i32)
     }
 
-    fn update(&mut self, play_id: i32, answer: bool) -> Result<(), Box<dyn Error>> {
-        let statement: &str = "UPDATE user_plays SET answer = :answer WHERE id = :id";
+    fn get_answered_since(
+        &mut self,
+        user_id: i32,
+        first_play_id: i32,
+    ) -> Result<Vec<UserPlay>, Box<dyn Error>> {
+        let statement: &str = "\
+            SELECT id, user_id, noun_id, exercise_type, answer, outcome, \
+                chosen_answer, answer_text, latency_ms \
+            FROM user_plays \
+            WHERE user_id = :user_id AND id >= :first_play_id AND answer IS NOT NULL \
+            ORDER BY id";
         let params: Params = params! {
-            "id" => play_id,
-            "answer" => answer,
+            "user_id" => user_id,
+            "first_play_id" => first_play_id,
         };
-        self.conn.exec_drop(statement, params).map_err(|e| e.into())
+        let rows: Vec<UserPlayRow> = self.conn()?.exec(statement, params)?;
+        rows.into_iter()
+            .filter_map(|row| Self::build(Some(row)).transpose())
+            .collect()
     }
 
-    fn remove(&mut self, play_id: i32) -> Result<(), Box<dyn Error>> {
-        let statement: &str = "DELETE FROM user_plays WHERE id = :id";
-        let params: Params = params! {"id" => play_id};
-        self.conn.exec_drop(statement, params).map_err(|e| e.into())
+    fn count_answers(&mut self, user_id: i32) -> Result<AnswerCount, Box<dyn Error>> {
+        let statement: &str = "\
+            SELECT COUNT(*), CAST(COALESCE(SUM(answer), 0) AS UNSIGNED) \
+            FROM user_plays \
+            WHERE user_id = :user_id AND answer IS NOT NULL";
+        let params: Params = params! {"user_id" => user_id};
+        self.conn()?
+            .exec_first(statement, params)
+            .map(Self::build_count)
+            .map_err(|e| e.into())
+    }
+
+    fn count_answers_by_gender(
+        &mut self,
+        user_id: i32,
+    ) -> Result<Vec<(Gender, AnswerCount)>, Box<dyn Error>> {
+        let statement: &str = "\
+            SELECT n.gender, COUNT(*), CAST(COALESCE(SUM(p.answer), 0) AS UNSIGNED) \
+            FROM user_plays p \
+            JOIN nouns n ON n.id = p.noun_id \
+            WHERE p.user_id = :user_id AND p.answer IS NOT NULL \
+            GROUP BY n.gender \
+            ORDER BY n.gender";
+        let params: Params = params! {"user_id" => user_id};
+        let rows: Vec<(String, u32, u32)> = self.conn()?.exec(statement, params)?;
+        rows.into_iter()
+            .map(|(gender_str, answered, correct)| {
+                let gender: Gender = gender_str.parse()?;
+                Ok((gender, AnswerCount { answered, correct }))
+            })
+            .collect()
+    }
+
+    fn count_gender_mistakes(
+        &mut self,
+        user_id: i32,
+    ) -> Result<Vec<GenderMistake>, Box<dyn Error>> {
+        let statement: &str = "\
+            SELECT n.gender, p.chosen_answer, COUNT(*) AS times \
+            FROM user_plays p \
+            JOIN nouns n ON n.id = p.noun_id \
+            WHERE p.user_id = :user_id \
+              AND p.answer = FALSE \
+              AND p.exercise_type = 'gender' \
+              AND p.chosen_answer <> :unknown \
+            GROUP BY n.gender, p.chosen_answer \
+            ORDER BY times DESC, n.gender, p.chosen_answer";
+        let params: Params = params! {
+            "user_id" => user_id,
+            "unknown" => UNKNOWN_ANSWER,
+        };
+        let rows: Vec<(String, String, u32)> = self.conn()?.exec(statement, params)?;
+        rows.into_iter()
+            .map(|(gender_str, chosen_str, times)| {
+                Ok(GenderMistake {
+                    gender: gender_str.parse()?,
+                    chosen: chosen_str.parse()?,
+                    times,
+                })
+            })
+            .collect()
+    }
+
+    fn count_recent_answers_by_rank(
+        &mut self,
+        user_id: i32,
+        min_rank: u32,
+        max_rank: Option<u32>,
+        limit: u32,
+    ) -> Result<AnswerCount, Box<dyn Error>> {
+        let statement: &str = "\
+            SELECT COUNT(*), CAST(COALESCE(SUM(recent.answer), 0) AS UNSIGNED) \
+            FROM ( \
+                SELECT p.answer \
+                FROM user_plays p \
+                JOIN nouns n ON n.id = p.noun_id \
+                WHERE p.user_id = :user_id \
+                  AND p.answer IS NOT NULL \
+                  AND n.frequency_rank > :min_rank \
+                  AND (:max_rank IS NULL OR n.frequency_rank <= :max_rank) \
+                ORDER BY p.id DESC \
+                LIMIT :limit \
+            ) recent";
+        let params: Params = params! {
+            "user_id" => user_id,
+            "min_rank" => min_rank,
+            "max_rank" => max_rank,
+            "limit" => limit,
+        };
+        self.conn()?
+            .exec_first(statement, params)
+            .map(Self::build_count)
+            .map_err(|e| e.into())
+    }
+
+    fn count_answered_on_local_day(
+        &mut self,
+        user_id: i32,
+        utc_offset_minutes: i32,
+        day: i64,
+    ) -> Result<u32, Box<dyn Error>> {
+        let statement: &str = "\
+            SELECT COUNT(*) \
+            FROM user_plays \
+            WHERE user_id = :user_id AND answer IS NOT NULL \
+                AND timestamp >= FROM_UNIXTIME(:day * 86400 - :utc_offset_minutes * 60) \
+                AND timestamp < FROM_UNIXTIME((:day + 1) * 86400 - :utc_offset_minutes * 60)";
+        let params: Params = params! {
+            "user_id" => user_id,
+            "utc_offset_minutes" => utc_offset_minutes,
+            "day" => day,
+        };
+        self.conn()?
+            .exec_first(statement, params)
+            .map(|count: Option<u32>| count.unwrap_or(0))
+            .map_err(|e| e.into())
+    }
+
+    fn count_answers_by_local_day(
+        &mut self,
+        user_id: i32,
+        utc_offset_minutes: i32,
+    ) -> Result<Vec<(i64, u32)>, Box<dyn Error>> {
+        let statement: &str = "\
+            SELECT FLOOR((UNIX_TIMESTAMP(timestamp) + :utc_offset_minutes * 60) / 86400) AS day, \
+                COUNT(*) \
+            FROM user_plays \
+            WHERE user_id = :user_id AND answer IS NOT NULL \
+            GROUP BY day \
+            ORDER BY day";
+        let params: Params = params! {
+            "user_id" => user_id,
+            "utc_offset_minutes" => utc_offset_minutes,
+        };
+        self.conn()?.exec(statement, params).map_err(|e| e.into())
+    }
+
+    fn get_mistaken_noun_ids(
+        &mut self,
+        user_id: i32,
+        required_correct: u32,
+    ) -> Result<Vec<i32>, Box<dyn Error>> {
+        let statement: &str = "\
+            SELECT w.noun_id \
+            FROM ( \
+                SELECT noun_id, MAX(id) AS last_wrong_id \
+                FROM user_plays \
+                WHERE user_id = :user_id AND answer = FALSE \
+                GROUP BY noun_id \
+            ) w \
+            LEFT JOIN user_plays c \
+                ON c.user_id = :user_id \
+                AND c.noun_id = w.noun_id \
+                AND c.answer = TRUE \
+                AND c.id > w.last_wrong_id \
+            GROUP BY w.noun_id, w.last_wrong_id \
+            HAVING COUNT(c.id) < :required_correct \
+            ORDER BY w.last_wrong_id DESC";
+        let params: Params = params! {
+            "user_id" => user_id,
+            "required_correct" => required_correct,
+        };
+        self.conn()?.exec(statement, params).map_err(|e| e.into())
+    }
+
+    fn get_current_streak(&mut self, user_id: i32) -> Result<u32, Box<dyn Error>> {
+        let statement: &str = "\
+            SELECT COUNT(*) \
+            FROM user_plays \
+            WHERE user_id = :user_id \
+              AND answer = TRUE \
+              AND id > COALESCE(( \
+                  SELECT MAX(id) FROM user_plays WHERE user_id = :user_id AND answer = FALSE \
+              ), 0)";
+        let params: Params = params! {"user_id" => user_id};
+        self.conn()?
+            .exec_first(statement, params)
+            .map(|count: Option<u32>| count.unwrap_or(0))
+            .map_err(|e| e.into())
     }
 }
@@ -1,47 +1,235 @@
-use crate::domain::User;
+use crate::domain::{ReminderCandidate, User, UserSettings};
 use crate::repository::connector;
-use mysql::{params, prelude::Queryable, Params, PooledConn};
+use mysql::{from_row_opt, params, prelude::Queryable, Params, Pool, PooledConn};
+use std::collections::HashMap;
 use std::error::Error;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+type UserRow = (
+    i32,
+    u64,
+    bool,
+    String,
+    u32,
+    bool,
+    Option<u16>,
+    i32,
+    String,
+    String,
+    String,
+);
+/// Last reminder and last answer times of a user, as whole unix seconds.
+type ReminderTimesRow = (i32, Option<u64>, Option<u64>);
 
 pub struct UsersRepository {
-    conn: PooledConn,
+    pool: Pool,
 }
 
 impl UsersRepository {
-    pub fn new() -> Self {
-        let conn: PooledConn = connector::new().unwrap();
-        UsersRepository { conn }
+    pub fn new(pool: Pool) -> Self {
+        UsersRepository { pool }
+    }
+
+    fn conn(&self) -> Result<PooledConn, Box<dyn Error>> {
+        connector::get_conn(&self.pool)
     }
 
-    fn build(result: Option<(i32, u64)>) -> Option<User> {
-        result.map(|(id, telegram_user_id)| User {
+    fn build(result: Option<UserRow>) -> Result<Option<User>, Box<dyn Error>> {
+        let Some((
+            id,
+            telegram_user_id,
+            show_correct_answer,
+            quiz_mode_str,
+            daily_goal,
+            streak_freeze,
+            reminder_time,
+            utc_offset_minutes,
+            ui_language_str,
+            difficulty_str,
+            language_str,
+        )) = result
+        else {
+            return Ok(None);
+        };
+        let settings = UserSettings {
+            quiz_mode: quiz_mode_str.parse()?,
+            show_correct_answer,
+            daily_goal,
+            streak_freeze,
+            reminder_time,
+            utc_offset_minutes,
+            ui_language: ui_language_str.parse()?,
+            difficulty: difficulty_str.parse()?,
+            language: language_str.parse()?,
+        };
+        Ok(Some(User {
             id,
             telegram_user_id,
-        })
+            settings,
+        }))
+    }
+
+    fn build_reminder_candidate(
+        user: User,
+        times: Option<&(Option<u64>, Option<u64>)>,
+    ) -> ReminderCandidate {
+        let to_time = |seconds: u64| UNIX_EPOCH + Duration::from_secs(seconds);
+        let (last_reminded_at, last_answered_at) = times.copied().unwrap_or_default();
+        ReminderCandidate {
+            user,
+            last_reminded_at: last_reminded_at.map(to_time),
+            last_answered_at: last_answered_at.map(to_time),
+        }
     }
 }
 
 pub trait UsersRepositoryTrait {
     fn get(&mut self, telegram_user_id: u64) -> Result<Option<User>, Box<dyn Error>>;
     fn insert(&mut self, user: &User) -> Result<(), Box<dyn Error>>;
+    fn update_settings(
+        &mut self,
+        user_id: i32,
+        settings: &UserSettings,
+    ) -> Result<(), Box<dyn Error>>;
+    fn get_reminder_candidates(&mut self) -> Result<Vec<ReminderCandidate>, Box<dyn Error>>;
+    fn update_last_reminded_at(
+        &mut self,
+        user_id: i32,
+        reminded_at: SystemTime,
+    ) -> Result<(), Box<dyn Error>>;
 }
 
 impl UsersRepositoryTrait for UsersRepository {
     fn get(&mut self, telegram_user_id: u64) -> Result<Option<User>, Box<dyn Error>> {
         let statement: &str = "\
-            SELECT id, telegram_user_id \
+            SELECT id, telegram_user_id, show_correct_answer, quiz_mode, daily_goal, \
+                streak_freeze, reminder_time, utc_offset_minutes, ui_language, difficulty, \
+                language \
             FROM users \
             WHERE telegram_user_id = :telegram_user_id";
         let params: Params = params! {"telegram_user_id" => telegram_user_id};
-        self.conn
+        self.conn()?
             .exec_first(statement, params)
-            .map(Self::build)
             .map_err(|e| e.into())
+            .and_then(Self::build)
     }
 
     fn insert(&mut self, user: &User) -> Result<(), Box<dyn Error>> {
-        let statement: &str = "INSERT INTO users (telegram_user_id) VALUES (:telegram_user_id)";
-        let params: Params = params! {"telegram_user_id" => user.telegram_user_id};
-        self.conn.exec_drop(statement, params).map_err(|e| e.into())
+        let statement: &str = "\
+            INSERT INTO users (telegram_user_id, show_correct_answer, quiz_mode, daily_goal, \
+                streak_freeze, reminder_time, utc_offset_minutes, ui_language, difficulty, \
+                language) \
+            VALUES (:telegram_user_id, :show_correct_answer, :quiz_mode, :daily_goal, \
+                :streak_freeze, :reminder_time, :utc_offset_minutes, :ui_language, :difficulty, \
+                :language)";
+        let settings: &UserSettings = &user.settings;
+        let params: Params = params! {
+            "telegram_user_id" => user.telegram_user_id,
+            "show_correct_answer" => settings.show_correct_answer,
+            "quiz_mode" => settings.quiz_mode.as_str(),
+            "daily_goal" => settings.daily_goal,
+            "streak_freeze" => settings.streak_freeze,
+            "reminder_time" => settings.reminder_time,
+            "utc_offset_minutes" => settings.utc_offset_minutes,
+            "ui_language" => settings.ui_language.as_str(),
+            "difficulty" => settings.difficulty.as_str(),
+            "language" => settings.language.as_str(),
+        };
+        self.conn()?
+            .exec_drop(statement, params)
+            .map_err(|e| e.into())
+    }
+
+    fn update_settings(
+        &mut self,
+        user_id: i32,
+        settings: &UserSettings,
+    ) -> Result<(), Box<dyn Error>> {
+        let statement: &str = "\
+            UPDATE users \
+            SET show_correct_answer = :show_correct_answer, \
+                quiz_mode = :quiz_mode, \
+                daily_goal = :daily_goal, \
+                streak_freeze = :streak_freeze, \
+                reminder_time = :reminder_time, \
+                utc_offset_minutes = :utc_offset_minutes, \
+                ui_language = :ui_language, \
+                difficulty = :difficulty, \
+                language = :language \
+            WHERE id = :id";
+        let params: Params = params! {
+            "id" => user_id,
+            "show_correct_answer" => settings.show_correct_answer,
+            "quiz_mode" => settings.quiz_mode.as_str(),
+            "daily_goal" => settings.daily_goal,
+            "streak_freeze" => settings.streak_freeze,
+            "reminder_time" => settings.reminder_time,
+            "utc_offset_minutes" => settings.utc_offset_minutes,
+            "ui_language" => settings.ui_language.as_str(),
+            "difficulty" => settings.difficulty.as_str(),
+            "language" => settings.language.as_str(),
+        };
+        self.conn()?
+            .exec_drop(statement, params)
+            .map_err(|e| e.into())
+    }
+
+    fn get_reminder_candidates(&mut self) -> Result<Vec<ReminderCandidate>, Box<dyn Error>> {
+        let users_statement: &str = "\
+            SELECT id, telegram_user_id, show_correct_answer, quiz_mode, daily_goal, \
+                streak_freeze, reminder_time, utc_offset_minutes, ui_language, difficulty, \
+                language \
+            FROM users \
+            WHERE reminder_time IS NOT NULL";
+        let times_statement: &str = "\
+            SELECT u.id, \
+                CAST(FLOOR(UNIX_TIMESTAMP(u.last_reminded_at)) AS UNSIGNED), \
+                ( \
+                    SELECT CAST(FLOOR(UNIX_TIMESTAMP(MAX(p.timestamp))) AS UNSIGNED) \
+                    FROM user_plays p \
+                    WHERE p.user_id = u.id AND p.answer IS NOT NULL \
+                ) \
+            FROM users u \
+            WHERE u.reminder_time IS NOT NULL";
+        let mut conn: PooledConn = self.conn()?;
+        let user_rows: Vec<UserRow> = conn.query(users_statement)?;
+        let times_rows: Vec<ReminderTimesRow> = conn
+            .query_map(times_statement, from_row_opt::<ReminderTimesRow>)?
+            .into_iter()
+            .collect::<Result<_, _>>()?;
+        let times: HashMap<i32, (Option<u64>, Option<u64>)> = times_rows
+            .into_iter()
+            .map(|(id, last_reminded_at, last_answered_at)| {
+                (id, (last_reminded_at, last_answered_at))
+            })
+            .collect();
+        user_rows
+            .into_iter()
+            .filter_map(|row| Self::build(Some(row)).transpose())
+            .map(|user| {
+                user.map(|user| {
+                    let user_times = times.get(&user.id);
+                    Self::build_reminder_candidate(user, user_times)
+                })
+            })
+            .collect()
+    }
+
+    fn update_last_reminded_at(
+        &mut self,
+        user_id: i32,
+        reminded_at: SystemTime,
+    ) -> Result<(), Box<dyn Error>> {
+        let statement: &str = "\
+            UPDATE users \
+            SET last_reminded_at = FROM_UNIXTIME(:reminded_at) \
+            WHERE id = :id";
+        let params: Params = params! {
+            "id" => user_id,
+            "reminded_at" => reminded_at.duration_since(UNIX_EPOCH)?.as_secs(),
+        };
+        self.conn()?
+            .exec_drop(statement, params)
+            .map_err(|e| e.into())
     }
 }
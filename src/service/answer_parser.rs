@@ -0,0 +1,130 @@
+use crate::domain::{Answer, Article, ExerciseType, Gender, Language};
+
+const MASCULINE_ANSWERS: [&str; 12] = [
+    "masculine",
+    "masc",
+    "m",
+    "male",
+    "masculino",
+    "masculina",
+    "masculin",
+    "maschile",
+    "mannlich",
+    "maskulin",
+    "чоловічий",
+    "ч",
+];
+const FEMININE_ANSWERS: [&str; 12] = [
+    "feminine",
+    "fem",
+    "f",
+    "female",
+    "femenino",
+    "femenina",
+    "feminin",
+    "femminile",
+    "weiblich",
+    "feminino",
+    "жіночий",
+    "ж",
+];
+const NEUTER_ANSWERS: [&str; 9] = [
+    "neuter",
+    "neut",
+    "n",
+    "neutral",
+    "neutrum",
+    "sachlich",
+    "neutro",
+    "середній",
+    "с",
+];
+const ANY_ANSWERS: [&str; 14] = [
+    "any",
+    "both",
+    "either",
+    "m/f",
+    "f/m",
+    "ambos",
+    "ambas",
+    "ambos generos",
+    "los dos",
+    "cualquiera",
+    "beides",
+    "beide",
+    "будь-який",
+    "обидва",
+];
+
+/// Reads a typed answer to an exercise of the given type on a noun of the
+/// given language, regardless of case, accents, surrounding whitespace and
+/// trailing punctuation.
+pub fn parse(text: &str, exercise_type: ExerciseType, language: Language) -> Option<Answer> {
+    let answer: String = normalize(text);
+    match exercise_type {
+        ExerciseType::Gender => parse_gender(&answer, language).map(Answer::Gender),
+        ExerciseType::SingularArticle | ExerciseType::PluralArticle => {
+            answer.parse::<Article>().ok().map(Answer::Article)
+        }
+    }
+}
+
+/// Articles of the language, abbreviations and gender names all name a
+/// gender, as long as the language has it.
+fn parse_gender(answer: &str, language: Language) -> Option<Gender> {
+    let article_gender: Option<Gender> = language
+        .gender_articles()
+        .iter()
+        .find(|(article, _)| *article == answer)
+        .map(|(_, gender)| *gender);
+    let gender: Gender = if let Some(gender) = article_gender {
+        gender
+    } else if MASCULINE_ANSWERS.contains(&answer) {
+        Gender::Masculine
+    } else if FEMININE_ANSWERS.contains(&answer) {
+        Gender::Feminine
+    } else if NEUTER_ANSWERS.contains(&answer) {
+        Gender::Neuter
+    } else if ANY_ANSWERS.contains(&answer) || names_both_articles(answer, language) {
+        Gender::Any
+    } else {
+        return None;
+    };
+    Some(gender).filter(|gender| language.answer_genders().contains(gender))
+}
+
+/// Both definite articles in either order, as in "el/la" or "la o el".
+fn names_both_articles(answer: &str, language: Language) -> bool {
+    let masculine: &str = language.definite_article(Gender::Masculine);
+    let feminine: &str = language.definite_article(Gender::Feminine);
+    [" o ", "/"].iter().any(|separator| {
+        answer == format!("{}{}{}", masculine, separator, feminine)
+            || answer == format!("{}{}{}", feminine, separator, masculine)
+    })
+}
+
+fn normalize(text: &str) -> String {
+    let folded: String = text
+        .trim()
+        .trim_end_matches(['.', '!', '?'])
+        .to_lowercase()
+        .chars()
+        .map(fold_accent)
+        .collect();
+    folded
+        .split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ")
+        .replace(" / ", "/")
+}
+
+fn fold_accent(c: char) -> char {
+    match c {
+        'á' | 'à' | 'â' | 'ä' => 'a',
+        'é' | 'è' | 'ê' | 'ë' => 'e',
+        'í' | 'ì' | 'î' | 'ï' => 'i',
+        'ó' | 'ò' | 'ô' | 'ö' => 'o',
+        'ú' | 'ù' | 'û' | 'ü' => 'u',
+        _ => c,
+    }
+}
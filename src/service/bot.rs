@@ -1,75 +1,800 @@
-use crate::client::telegram::TelegramClient;
-use crate::domain::{Noun, User, UserPlay};
-use crate::repository::nouns::{NounsRepository, NounsRepositoryTrait};
-use crate::repository::user_plays::{UserPlaysRepository, UserPlaysRepositoryTrait};
-use crate::repository::users::{UsersRepository, UsersRepositoryTrait};
-use frankenstein::{Message, Update, UpdateContent};
+use crate::client::chat::{ChatClient, ChatEvent, Choice, IncomingChoice, IncomingMessage};
+use crate::domain::{
+    Answer, AnswerCount, Article, ExerciseType, Gender, GenderMistake, Language, MistakesSession,
+    Noun, NounAccuracy, NounDifficulty, NounReview, PlayOutcome, PlayResponse, QuizMode,
+    ReminderCandidate, Session, UiLanguage, User, UserPlay, UserSettings, UserStats,
+};
+use crate::i18n;
+use crate::repository::analytics::AnalyticsRepositoryTrait;
+use crate::repository::mistake_sessions::MistakeSessionsRepositoryTrait;
+use crate::repository::noun_reviews::NounReviewsRepositoryTrait;
+use crate::repository::nouns::NounsRepositoryTrait;
+use crate::repository::sessions::SessionsRepositoryTrait;
+use crate::repository::user_plays::UserPlaysRepositoryTrait;
+use crate::repository::users::UsersRepositoryTrait;
+use crate::repository::Repositories;
+use crate::service::settings::SettingsAction;
+use crate::service::streaks::Streaks;
+use crate::service::weakness::EndingAccuracy;
+use crate::service::{
+    answer_parser, gender_rules, mistakes, noun_difficulty, reminders, sessions, settings,
+    spaced_repetition, streaks, weakness,
+};
 use rand::Rng;
 use std::env::VarError::NotPresent;
 use std::error::Error;
 use std::sync::mpsc::Receiver;
+use std::time::SystemTime;
 
-pub struct BotService {
-    telegram_client: TelegramClient,
-    users_repo: UsersRepository,
-    nouns_repo: NounsRepository,
-    user_plays_repo: UserPlaysRepository,
+/// Answer data of the choices ending a play without answering it.
+const SKIP_DATA: &str = "skip";
+const UNKNOWN_DATA: &str = "unknown";
+/// Answers on the nouns a level introduces that are checked for promotion.
+const PROMOTION_WINDOW: u32 = 30;
+/// Accuracy, in percent, needed over that window to move up a level.
+const PROMOTION_ACCURACY: f64 = 90.0;
+
+pub struct BotService<C: ChatClient> {
+    chat_client: C,
+    users_repo: Box<dyn UsersRepositoryTrait>,
+    nouns_repo: Box<dyn NounsRepositoryTrait>,
+    user_plays_repo: Box<dyn UserPlaysRepositoryTrait>,
+    noun_reviews_repo: Box<dyn NounReviewsRepositoryTrait>,
+    sessions_repo: Box<dyn SessionsRepositoryTrait>,
+    analytics_repo: Box<dyn AnalyticsRepositoryTrait>,
+    mistake_sessions_repo: Box<dyn MistakeSessionsRepositoryTrait>,
+    admin_user_ids: Vec<u64>,
 }
 
-impl BotService {
-    pub fn new(telegram_client: TelegramClient) -> Self {
+impl<C: ChatClient> BotService<C> {
+    pub fn new(chat_client: C, repositories: Repositories) -> Self {
         Self {
-            telegram_client,
-            users_repo: UsersRepository::new(),
-            nouns_repo: NounsRepository::new(),
-            user_plays_repo: UserPlaysRepository::new(),
+            chat_client,
+            users_repo: repositories.users,
+            nouns_repo: repositories.nouns,
+            user_plays_repo: repositories.user_plays,
+            noun_reviews_repo: repositories.noun_reviews,
+            sessions_repo: repositories.sessions,
+            analytics_repo: repositories.analytics,
+            mistake_sessions_repo: repositories.mistake_sessions,
+            admin_user_ids: Vec::new(),
         }
     }
 
-    pub fn run(&mut self, update_receiver: Receiver<Update>) -> Result<(), Box<dyn Error>> {
-        for update in update_receiver {
-            if let UpdateContent::Message(message) = update.content {
-                self.handle_update(message)
-                    .inspect_err(|e| eprintln!("Error handling update: {}", e))
-                    .ok();
-            }
+    /// Lets the given Telegram users run the admin commands, such as /hardest.
+    pub fn with_admins(mut self, admin_user_ids: Vec<u64>) -> Self {
+        self.admin_user_ids = admin_user_ids;
+        self
+    }
+
+    pub fn run(&mut self, event_receiver: Receiver<ChatEvent>) -> Result<(), Box<dyn Error>> {
+        for event in event_receiver {
+            let result: Result<(), Box<dyn Error>> = match event {
+                ChatEvent::Message(message) => self.handle_message(message),
+                ChatEvent::Choice(choice) => self.handle_choice(choice),
+                ChatEvent::Tick(now) => self.send_due_reminders(now),
+            };
+            result
+                .inspect_err(|e| eprintln!("Error handling update: {}", e))
+                .ok();
         }
         Ok(())
     }
 
-    fn handle_update(&mut self, message: Message) -> Result<(), Box<dyn Error>> {
-        let chat_id: i64 = message.chat.id;
-        let telegram_user_id: u64 = message.from.unwrap().id;
+    fn handle_message(&mut self, message: IncomingMessage) -> Result<(), Box<dyn Error>> {
+        let chat_id: i64 = message.chat_id;
+        let telegram_user_id: u64 = message.user_id;
         let message_id: i32 = message.message_id;
-        self.ensure_user_exists(telegram_user_id)?;
+        self.ensure_user_exists(telegram_user_id, message.language_code.as_deref())?;
+
+        match message.text.as_str() {
+            "/stop" => self.handle_stop_command(chat_id, telegram_user_id),
+            "/help" => self.handle_help_command(chat_id, telegram_user_id),
+            "/start" => self.handle_start_command(chat_id, telegram_user_id),
+            "/stats" => self.handle_stats_command(chat_id, telegram_user_id),
+            "/reveal" => self.handle_reveal_command(chat_id, telegram_user_id),
+            "/mode" => self.handle_mode_command(chat_id, telegram_user_id),
+            "/language" => self.handle_language_command(chat_id, telegram_user_id),
+            "/lang" => self.handle_lang_command(chat_id, telegram_user_id),
+            "/settings" => self.handle_settings_command(chat_id, telegram_user_id),
+            "/mistakes" => self.handle_mistakes_command(chat_id, telegram_user_id),
+            "/why" => self.handle_why_command(chat_id, telegram_user_id),
+            "/streak" => self.handle_streak_command(chat_id, telegram_user_id),
+            "/weak" => self.handle_weak_command(chat_id, telegram_user_id),
+            text if text == "/hardest" || text.starts_with("/hardest ") => {
+                let argument: &str = &text["/hardest".len()..];
+                self.handle_hardest_command(chat_id, telegram_user_id, argument)
+            }
+            text => self.handle_text_answer(text, chat_id, telegram_user_id, message_id),
+        }
+    }
+
+    fn handle_choice(&mut self, choice: IncomingChoice) -> Result<(), Box<dyn Error>> {
+        self.ensure_user_exists(choice.user_id, choice.language_code.as_deref())?;
+
+        if let Some(quiz_mode) = Self::parse_mode_data(&choice.data) {
+            return self.handle_mode_choice(choice, quiz_mode);
+        }
+        if let Some(language) = Self::parse_language_data(&choice.data) {
+            return self.handle_language_choice(choice, language);
+        }
+        if let Some(ui_language) = Self::parse_lang_data(&choice.data) {
+            return self.handle_lang_choice(choice, ui_language);
+        }
+        if let Some(action) = settings::parse_action(&choice.data) {
+            return self.handle_settings_choice(choice, action?);
+        }
+        if choice.data.starts_with("reminder:") {
+            return self.handle_reminder_choice(choice);
+        }
+        self.handle_answer_choice(choice)
+    }
+
+    /// Nudges the users whose reminder is due. They are marked as reminded
+    /// before sending, so a user who blocked the bot isn't retried every tick.
+    fn send_due_reminders(&mut self, now: SystemTime) -> Result<(), Box<dyn Error>> {
+        let candidates: Vec<ReminderCandidate> = self.users_repo.get_reminder_candidates()?;
+        for candidate in candidates
+            .iter()
+            .filter(|candidate| reminders::is_due(candidate, now))
+        {
+            let user: &User = &candidate.user;
+            self.users_repo.update_last_reminded_at(user.id, now)?;
+            let (text, rows) = reminders::message(&user.settings);
+            // Private chats share their id with the user.
+            let chat_id: i64 = user.telegram_user_id as i64;
+            self.chat_client
+                .send_menu(chat_id, &text, &rows)
+                .inspect_err(|e| eprintln!("Failed to remind {}: {}", user.telegram_user_id, e))
+                .ok();
+        }
+        Ok(())
+    }
+
+    fn handle_reminder_choice(&mut self, choice: IncomingChoice) -> Result<(), Box<dyn Error>> {
+        match choice.data.as_str() {
+            reminders::PLAY_NOW_DATA => {
+                self.chat_client.acknowledge_choice(&choice.id, None)?;
+                let text: String = choice.question_text.unwrap_or_default();
+                self.chat_client
+                    .edit_message(choice.chat_id, choice.message_id, &text)?;
+                self.send_next_guess(choice.chat_id, choice.user_id)
+            }
+            reminders::TURN_OFF_DATA => {
+                let user: User = self.get_user(choice.user_id)?;
+                let settings = UserSettings {
+                    reminder_time: None,
+                    ..user.settings
+                };
+                self.users_repo.update_settings(user.id, &settings)?;
+                self.chat_client.acknowledge_choice(&choice.id, None)?;
+                let text: String = i18n::text(settings.ui_language, "reminders-off");
+                self.chat_client
+                    .edit_message(choice.chat_id, choice.message_id, &text)
+            }
+            data => Err(format!("Unexpected choice data '{}'", data).into()),
+        }
+    }
+
+    fn handle_answer_choice(&mut self, choice: IncomingChoice) -> Result<(), Box<dyn Error>> {
+        let telegram_user_id: u64 = choice.user_id;
+        let chat_id: i64 = choice.chat_id;
+        let message_id: i32 = choice.message_id;
+        let unexpected_data = || format!("Unexpected choice data '{}'", choice.data);
+
+        let (play_id, answer_str) =
+            Self::parse_answer_data(&choice.data).ok_or_else(unexpected_data)?;
+
+        let user: User = self.get_user(telegram_user_id)?;
+        let lang: UiLanguage = user.settings.ui_language;
+        let current_play: Option<UserPlay> = self
+            .user_plays_repo
+            .get(play_id)?
+            .filter(|play| play.user_id == user.id && play.outcome.is_none());
+        let Some(current_play) = current_play else {
+            let text: String = i18n::text(lang, "question-expired");
+            self.chat_client
+                .acknowledge_choice(&choice.id, Some(&text))?;
+            return Ok(());
+        };
+        match answer_str {
+            SKIP_DATA => {
+                return self.handle_unanswered_choice(
+                    choice,
+                    &user,
+                    current_play,
+                    PlayOutcome::Skipped,
+                )
+            }
+            UNKNOWN_DATA => {
+                return self.handle_unanswered_choice(
+                    choice,
+                    &user,
+                    current_play,
+                    PlayOutcome::Unknown,
+                )
+            }
+            _ => {}
+        }
+        let answer: Answer =
+            Answer::parse(current_play.exercise_type, answer_str).ok_or_else(unexpected_data)?;
+
+        let (playing_noun, is_correct_guess) = self.grade_play(&current_play, &answer, None)?;
+        self.chat_client.acknowledge_choice(&choice.id, None)?;
+
+        let verdict: &str = if is_correct_guess { "✅" } else { "❌" };
+        let edited_text: String = format!(
+            "{}\n\n{} {}",
+            choice.question_text.unwrap_or_default(),
+            verdict,
+            Self::answer_label(lang, &answer)
+        );
+        self.chat_client
+            .edit_message(chat_id, message_id, &edited_text)?;
+
+        if !is_correct_guess && user.settings.show_correct_answer {
+            self.send_correct_answer(chat_id, message_id, &playing_noun, &current_play, lang)?;
+        }
+        self.send_goal_progress(chat_id, &user)?;
+        self.send_next_guess(chat_id, telegram_user_id)
+    }
+
+    /// Ends the play without grading it. "I don't know" reveals the answer and
+    /// brings the noun back for review, while a skipped noun is left alone.
+    fn handle_unanswered_choice(
+        &mut self,
+        choice: IncomingChoice,
+        user: &User,
+        play: UserPlay,
+        outcome: PlayOutcome,
+    ) -> Result<(), Box<dyn Error>> {
+        let lang: UiLanguage = user.settings.ui_language;
+        let noun: Noun = self.nouns_repo.get(play.noun_id)?;
+        let response = PlayResponse {
+            outcome,
+            chosen_answer: None,
+            answer_text: None,
+        };
+        self.user_plays_repo.update(play.id, &response)?;
+        self.track_session(&play)?;
+        self.chat_client.acknowledge_choice(&choice.id, None)?;
+
+        let label: String = if outcome == PlayOutcome::Skipped {
+            i18n::text(lang, "answer-skipped")
+        } else {
+            self.schedule_review(play.user_id, noun.id, false)?;
+            self.update_mistakes_session(play.user_id, |session| session.postpone(noun.id))?;
+            i18n::text(lang, "choice-unknown")
+        };
+        let edited_text: String =
+            format!("{}\n\n{}", choice.question_text.unwrap_or_default(), label);
+        self.chat_client
+            .edit_message(choice.chat_id, choice.message_id, &edited_text)?;
+        if outcome == PlayOutcome::Unknown {
+            self.send_correct_answer(choice.chat_id, choice.message_id, &noun, &play, lang)?;
+        }
+        self.send_next_guess(choice.chat_id, choice.user_id)
+    }
+
+    /// Skip and "I don't know", on a row of their own below the answers.
+    fn build_action_choices(play_id: i32, ui_language: UiLanguage) -> Vec<Choice> {
+        [("choice-skip", SKIP_DATA), ("choice-unknown", UNKNOWN_DATA)]
+            .iter()
+            .map(|(key, action)| {
+                let label: String = i18n::text(ui_language, key);
+                Choice::new(&label, format!("answer:{}:{}", play_id, action))
+            })
+            .collect()
+    }
+
+    /// Answer choices carry the play they belong to, so answers to an
+    /// outdated question can be told apart from the current one.
+    fn build_answer_choices(
+        play_id: i32,
+        language: Language,
+        ui_language: UiLanguage,
+    ) -> Vec<Choice> {
+        language
+            .answer_genders()
+            .iter()
+            .map(|gender| {
+                let data: String = format!("answer:{}:{}", play_id, gender.as_str());
+                Choice::new(&i18n::gender(ui_language, *gender), data)
+            })
+            .collect()
+    }
+
+    /// Answer as shown under the question once picked.
+    fn answer_label(ui_language: UiLanguage, answer: &Answer) -> String {
+        match answer {
+            Answer::Gender(gender) => i18n::gender(ui_language, *gender),
+            Answer::Article(article) => article.to_string(),
+        }
+    }
+
+    fn build_article_choices(play_id: i32, articles: &[Article]) -> Vec<Choice> {
+        articles
+            .iter()
+            .map(|article| {
+                let data: String = format!("answer:{}:{}", play_id, article.as_str());
+                Choice::new(article.as_str(), data)
+            })
+            .collect()
+    }
+
+    fn parse_answer_data(data: &str) -> Option<(i32, &str)> {
+        let mut parts = data.splitn(3, ':');
+        match (parts.next(), parts.next(), parts.next()) {
+            (Some("answer"), Some(play_id), Some(answer)) => {
+                play_id.parse().ok().map(|play_id| (play_id, answer))
+            }
+            _ => None,
+        }
+    }
+
+    fn parse_mode_data(data: &str) -> Option<QuizMode> {
+        data.strip_prefix("mode:")?.parse().ok()
+    }
+
+    fn handle_mode_command(
+        &mut self,
+        chat_id: i64,
+        telegram_user_id: u64,
+    ) -> Result<(), Box<dyn Error>> {
+        let lang: UiLanguage = self.get_user(telegram_user_id)?.settings.ui_language;
+        let choices: Vec<Choice> = QuizMode::ALL
+            .iter()
+            .map(|quiz_mode| {
+                let data: String = format!("mode:{}", quiz_mode.as_str());
+                Choice::new(&i18n::quiz_mode(lang, *quiz_mode), data)
+            })
+            .collect();
+        let text: String = i18n::text(lang, "mode-question");
+        self.chat_client.send_question(chat_id, &text, &choices)
+    }
+
+    fn handle_mode_choice(
+        &mut self,
+        choice: IncomingChoice,
+        quiz_mode: QuizMode,
+    ) -> Result<(), Box<dyn Error>> {
+        let user: User = self.get_user(choice.user_id)?;
+        let settings = UserSettings {
+            quiz_mode,
+            ..user.settings
+        };
+        self.users_repo.update_settings(user.id, &settings)?;
+        self.chat_client.acknowledge_choice(&choice.id, None)?;
+
+        let lang: UiLanguage = settings.ui_language;
+        let text: String = i18n::format(
+            lang,
+            "mode-chosen",
+            &[("mode", &i18n::quiz_mode(lang, quiz_mode))],
+        );
+        self.chat_client
+            .edit_message(choice.chat_id, choice.message_id, &text)
+    }
+
+    fn parse_language_data(data: &str) -> Option<Language> {
+        data.strip_prefix("language:")?.parse().ok()
+    }
+
+    fn handle_language_command(
+        &mut self,
+        chat_id: i64,
+        telegram_user_id: u64,
+    ) -> Result<(), Box<dyn Error>> {
+        let lang: UiLanguage = self.get_user(telegram_user_id)?.settings.ui_language;
+        let choices: Vec<Choice> = Language::ALL
+            .iter()
+            .map(|language| {
+                let label: String = format!(
+                    "{} {}",
+                    language.flag(),
+                    i18n::learning_language(lang, *language)
+                );
+                Choice::new(&label, format!("language:{}", language.as_str()))
+            })
+            .collect();
+        let text: String = i18n::text(lang, "language-question");
+        self.chat_client.send_question(chat_id, &text, &choices)
+    }
+
+    fn handle_language_choice(
+        &mut self,
+        choice: IncomingChoice,
+        language: Language,
+    ) -> Result<(), Box<dyn Error>> {
+        let user: User = self.get_user(choice.user_id)?;
+        let settings = UserSettings {
+            language,
+            ..user.settings
+        };
+        self.users_repo.update_settings(user.id, &settings)?;
+        self.chat_client.acknowledge_choice(&choice.id, None)?;
 
-        match message.text.as_deref() {
-            Some("/stop") => self.handle_stop_command(chat_id, telegram_user_id),
-            Some("/help") => self.handle_help_command(chat_id),
-            Some("/start") => self.handle_start_command(chat_id, telegram_user_id),
-            Some("/stats") => self.handle_stats_command(chat_id),
-            Some(text) => self.handle_text_answer(text, chat_id, telegram_user_id, message_id),
-            _ => Ok(()),
+        let lang: UiLanguage = settings.ui_language;
+        let mut text: String = i18n::format(
+            lang,
+            "language-chosen",
+            &[
+                ("flag", &language.flag()),
+                ("language", &i18n::learning_language(lang, language)),
+            ],
+        );
+        if language != Language::Spanish && settings.quiz_mode != QuizMode::Gender {
+            text.push('\n');
+            text.push_str(&i18n::text(lang, "language-articles-spanish-only"));
         }
+        self.chat_client
+            .edit_message(choice.chat_id, choice.message_id, &text)
+    }
+
+    fn parse_lang_data(data: &str) -> Option<UiLanguage> {
+        data.strip_prefix("lang:")?.parse().ok()
+    }
+
+    /// Offers the interface languages under their native names, so they can
+    /// be found whatever the current one is.
+    fn handle_lang_command(
+        &mut self,
+        chat_id: i64,
+        telegram_user_id: u64,
+    ) -> Result<(), Box<dyn Error>> {
+        let lang: UiLanguage = self.get_user(telegram_user_id)?.settings.ui_language;
+        let choices: Vec<Choice> = UiLanguage::ALL
+            .iter()
+            .map(|ui_language| {
+                let data: String = format!("lang:{}", ui_language.as_str());
+                Choice::new(&ui_language.to_string(), data)
+            })
+            .collect();
+        let text: String = i18n::text(lang, "lang-question");
+        self.chat_client.send_question(chat_id, &text, &choices)
+    }
+
+    fn handle_lang_choice(
+        &mut self,
+        choice: IncomingChoice,
+        ui_language: UiLanguage,
+    ) -> Result<(), Box<dyn Error>> {
+        let user: User = self.get_user(choice.user_id)?;
+        let settings = UserSettings {
+            ui_language,
+            ..user.settings
+        };
+        self.users_repo.update_settings(user.id, &settings)?;
+        self.chat_client.acknowledge_choice(&choice.id, None)?;
+
+        let text: String = i18n::text(ui_language, "lang-chosen");
+        self.chat_client
+            .edit_message(choice.chat_id, choice.message_id, &text)
     }
 
-    fn handle_stats_command(&mut self, chat_id: i64) -> Result<(), Box<dyn Error>> {
-        let welcome_message = "Coming soon";
-        self.telegram_client
-            .send_message(chat_id, welcome_message)?;
+    fn handle_stats_command(
+        &mut self,
+        chat_id: i64,
+        telegram_user_id: u64,
+    ) -> Result<(), Box<dyn Error>> {
+        let user: User = self.get_user(telegram_user_id)?;
+        let stats: UserStats = self.get_stats(&user)?;
+        let text: String = Self::format_stats(&stats, user.settings.ui_language);
+        self.chat_client.send_message(chat_id, &text)?;
         Ok(())
     }
 
+    fn handle_streak_command(
+        &mut self,
+        chat_id: i64,
+        telegram_user_id: u64,
+    ) -> Result<(), Box<dyn Error>> {
+        let user: User = self.get_user(telegram_user_id)?;
+        let streaks: Streaks = self.get_streaks(&user)?;
+        let text: String = streaks::summary(&streaks, &user.settings);
+        self.chat_client.send_message(chat_id, &text)
+    }
+
+    /// Reports where the user goes wrong in the language they are learning.
+    fn handle_weak_command(
+        &mut self,
+        chat_id: i64,
+        telegram_user_id: u64,
+    ) -> Result<(), Box<dyn Error>> {
+        let user: User = self.get_user(telegram_user_id)?;
+        let lang: UiLanguage = user.settings.ui_language;
+        let language: Language = user.settings.language;
+        let accuracies: Vec<NounAccuracy> = self
+            .analytics_repo
+            .count_answers_by_noun(user.id, language)?;
+        if accuracies.is_empty() {
+            let learning: String = i18n::learning_language(lang, language);
+            let text: String = i18n::format(lang, "weak-none", &[("language", &learning)]);
+            return self.chat_client.send_message(chat_id, &text);
+        }
+        let confusion: Vec<GenderMistake> = self
+            .analytics_repo
+            .count_gender_confusion(user.id, language)?;
+        let endings: Vec<EndingAccuracy> = weakness::weak_endings(&accuracies);
+        let nouns: Vec<(Noun, AnswerCount)> = weakness::weak_nouns(&accuracies)
+            .into_iter()
+            .map(|accuracy| {
+                Ok((
+                    self.nouns_repo.get(accuracy.noun_id)?,
+                    accuracy.count.clone(),
+                ))
+            })
+            .collect::<Result<_, Box<dyn Error>>>()?;
+        let text: String = weakness::report(lang, language, &confusion, &endings, &nouns);
+        self.chat_client.send_message(chat_id, &text)
+    }
+
+    fn handle_hardest_command(
+        &mut self,
+        chat_id: i64,
+        telegram_user_id: u64,
+        argument: &str,
+    ) -> Result<(), Box<dyn Error>> {
+        let user: User = self.get_user(telegram_user_id)?;
+        let lang: UiLanguage = user.settings.ui_language;
+        if !self.admin_user_ids.contains(&telegram_user_id) {
+            let text: String = i18n::text(lang, "hardest-admins-only");
+            return self.chat_client.send_message(chat_id, &text);
+        }
+        let Ok(size) = noun_difficulty::parse_report_size(Some(argument)) else {
+            let text: String = i18n::text(lang, "hardest-usage");
+            return self.chat_client.send_message(chat_id, &text);
+        };
+        let hardest: Vec<NounDifficulty> = self
+            .analytics_repo
+            .get_hardest_nouns(noun_difficulty::MIN_ANSWERS, size)?;
+        let easiest: Vec<NounDifficulty> = self
+            .analytics_repo
+            .get_easiest_nouns(noun_difficulty::MIN_ANSWERS, size)?;
+        let hardest: Vec<(Noun, NounDifficulty)> = self.with_nouns(hardest)?;
+        let easiest: Vec<(Noun, NounDifficulty)> = self.with_nouns(easiest)?;
+        let text: String = noun_difficulty::report(lang, &hardest, &easiest);
+        self.chat_client.send_message(chat_id, &text)
+    }
+
+    fn with_nouns(
+        &mut self,
+        difficulties: Vec<NounDifficulty>,
+    ) -> Result<Vec<(Noun, NounDifficulty)>, Box<dyn Error>> {
+        difficulties
+            .into_iter()
+            .map(|difficulty| Ok((self.nouns_repo.get(difficulty.noun_id)?, difficulty)))
+            .collect()
+    }
+
+    fn handle_reveal_command(
+        &mut self,
+        chat_id: i64,
+        telegram_user_id: u64,
+    ) -> Result<(), Box<dyn Error>> {
+        let user: User = self.get_user(telegram_user_id)?;
+        let show_correct_answer: bool = !user.settings.show_correct_answer;
+        let settings = UserSettings {
+            show_correct_answer,
+            ..user.settings
+        };
+        self.users_repo.update_settings(user.id, &settings)?;
+
+        let key: &str = if show_correct_answer {
+            "reveal-on"
+        } else {
+            "reveal-off"
+        };
+        let text: String = i18n::text(settings.ui_language, key);
+        self.chat_client.send_message(chat_id, &text)?;
+        Ok(())
+    }
+
+    fn handle_why_command(
+        &mut self,
+        chat_id: i64,
+        telegram_user_id: u64,
+    ) -> Result<(), Box<dyn Error>> {
+        let user: User = self.get_user(telegram_user_id)?;
+        let lang: UiLanguage = user.settings.ui_language;
+        let Some(play) = self.user_plays_repo.get_last_answered(user.id)? else {
+            let text: String = i18n::text(lang, "why-no-answer");
+            return self.chat_client.send_message(chat_id, &text);
+        };
+        let noun: Noun = self.nouns_repo.get(play.noun_id)?;
+        let explanation: String = gender_rules::explain(&noun, lang).unwrap_or_else(|| {
+            let language: String = i18n::learning_language(lang, noun.language);
+            i18n::format(lang, "why-no-rules", &[("language", &language)])
+        });
+        let text: String = format!(
+            "{}\n{}",
+            i18n::format(
+                lang,
+                "correct-answer",
+                &[
+                    ("noun", &noun.with_article()),
+                    ("gender", &i18n::gender_in_sentence(lang, noun.gender)),
+                ],
+            ),
+            explanation
+        );
+        self.chat_client.send_message(chat_id, &text)
+    }
+
+    fn handle_settings_command(
+        &mut self,
+        chat_id: i64,
+        telegram_user_id: u64,
+    ) -> Result<(), Box<dyn Error>> {
+        let user: User = self.get_user(telegram_user_id)?;
+        let (text, rows) = settings::menu(&user.settings);
+        self.chat_client.send_menu(chat_id, &text, &rows)
+    }
+
+    /// Saves the picked option and redraws the menu in place, or replaces it
+    /// with a summary once done.
+    fn handle_settings_choice(
+        &mut self,
+        choice: IncomingChoice,
+        action: SettingsAction,
+    ) -> Result<(), Box<dyn Error>> {
+        let user: User = self.get_user(choice.user_id)?;
+        let updated: UserSettings = settings::apply(&user.settings, action);
+        if updated != user.settings {
+            self.users_repo.update_settings(user.id, &updated)?;
+        }
+        self.chat_client.acknowledge_choice(&choice.id, None)?;
+
+        if action == SettingsAction::Done {
+            let text: String = settings::summary(&updated);
+            return self
+                .chat_client
+                .edit_message(choice.chat_id, choice.message_id, &text);
+        }
+        let (text, rows) = settings::menu(&updated);
+        self.chat_client
+            .edit_menu(choice.chat_id, choice.message_id, &text, &rows)
+    }
+
+    /// "Answered today" counts the user's local day, as the daily goal does.
+    fn get_stats(&mut self, user: &User) -> Result<UserStats, Box<dyn Error>> {
+        let user_id: i32 = user.id;
+        let settings: &UserSettings = &user.settings;
+        let today: i64 = settings.local_day(SystemTime::now());
+        Ok(UserStats {
+            overall: self.user_plays_repo.count_answers(user_id)?,
+            by_gender: self.user_plays_repo.count_answers_by_gender(user_id)?,
+            gender_mistakes: self.user_plays_repo.count_gender_mistakes(user_id)?,
+            answered_today: self.user_plays_repo.count_answered_on_local_day(
+                user_id,
+                settings.utc_offset_minutes,
+                today,
+            )?,
+            current_streak: self.user_plays_repo.get_current_streak(user_id)?,
+        })
+    }
+
+    fn format_stats(stats: &UserStats, ui_language: UiLanguage) -> String {
+        if stats.overall.answered == 0 {
+            return i18n::text(ui_language, "stats-none");
+        }
+
+        let format_count = |count: &AnswerCount| -> String {
+            format!(
+                "{:.0}% ({}/{})",
+                count.accuracy(),
+                count.correct,
+                count.answered
+            )
+        };
+        let by_gender: String = stats
+            .by_gender
+            .iter()
+            .map(|(gender, count)| {
+                let usual_mistake: String = stats
+                    .gender_mistakes
+                    .iter()
+                    .find(|mistake| mistake.gender == *gender)
+                    .map(|mistake| {
+                        let chosen: String = i18n::gender(ui_language, mistake.chosen);
+                        i18n::format(ui_language, "stats-usual-mistake", &[("gender", &chosen)])
+                    })
+                    .map_or_else(String::new, |mistake| format!(", {}", mistake));
+                let gender: String = i18n::gender(ui_language, *gender);
+                format!("  {}: {}{}\n", gender, format_count(count), usual_mistake)
+            })
+            .collect();
+
+        i18n::format(
+            ui_language,
+            "stats",
+            &[
+                ("answered", &stats.overall.answered),
+                ("accuracy", &format_count(&stats.overall)),
+                ("by_gender", &by_gender),
+                ("today", &stats.answered_today),
+                ("streak", &stats.current_streak),
+            ],
+        )
+    }
+
     fn handle_start_command(
         &mut self,
         chat_id: i64,
         telegram_user_id: u64,
     ) -> Result<(), Box<dyn Error>> {
-        self.send_welcome_message(chat_id)?;
+        self.finish_mistakes_session(chat_id, telegram_user_id)?;
+        let user: User = self.get_user(telegram_user_id)?;
+        self.end_session(user.id)?;
+        self.send_welcome_message(chat_id, &user.settings)?;
         self.send_next_guess(chat_id, telegram_user_id)?;
         Ok(())
     }
 
+    fn handle_mistakes_command(
+        &mut self,
+        chat_id: i64,
+        telegram_user_id: u64,
+    ) -> Result<(), Box<dyn Error>> {
+        let user: User = self.get_user(telegram_user_id)?;
+        let lang: UiLanguage = user.settings.ui_language;
+        let noun_ids: Vec<i32> = self
+            .user_plays_repo
+            .get_mistaken_noun_ids(user.id, mistakes::REQUIRED_CORRECT_ANSWERS)?;
+        if noun_ids.is_empty() {
+            let text: String = i18n::text(lang, "mistakes-none");
+            return self.chat_client.send_message(chat_id, &text);
+        }
+
+        let text: String = i18n::plural(lang, "mistakes-start", noun_ids.len() as u32, &[]);
+        self.chat_client.send_message(chat_id, &text)?;
+        self.mistake_sessions_repo
+            .upsert(user.id, &MistakesSession::new(noun_ids))?;
+        self.send_next_guess(chat_id, telegram_user_id)
+    }
+
+    /// Applies `change` to the user's mistakes review, if one is under way,
+    /// and saves it.
+    fn update_mistakes_session<T>(
+        &mut self,
+        user_id: i32,
+        change: impl FnOnce(&mut MistakesSession) -> T,
+    ) -> Result<Option<T>, Box<dyn Error>> {
+        let Some(mut session) = self.mistake_sessions_repo.get(user_id)? else {
+            return Ok(None);
+        };
+        let changed: T = change(&mut session);
+        self.mistake_sessions_repo.upsert(user_id, &session)?;
+        Ok(Some(changed))
+    }
+
+    fn take_mistakes_session(
+        &mut self,
+        user_id: i32,
+    ) -> Result<Option<MistakesSession>, Box<dyn Error>> {
+        let session: Option<MistakesSession> = self.mistake_sessions_repo.get(user_id)?;
+        if session.is_some() {
+            self.mistake_sessions_repo.remove(user_id)?;
+        }
+        Ok(session)
+    }
+
+    /// Ends the user's mistakes review early, if any, with its summary.
+    fn finish_mistakes_session(
+        &mut self,
+        chat_id: i64,
+        telegram_user_id: u64,
+    ) -> Result<(), Box<dyn Error>> {
+        let user: User = self.get_user(telegram_user_id)?;
+        match self.take_mistakes_session(user.id)? {
+            Some(session) => {
+                let text: String = session.summary(user.settings.ui_language);
+                self.chat_client.send_message(chat_id, &text)
+            }
+            None => Ok(()),
+        }
+    }
+
     fn handle_stop_command(
         &mut self,
         chat_id: i64,
@@ -77,69 +802,301 @@ impl BotService {
     ) -> Result<(), Box<dyn Error>> {
         println!("Stopping the game for {}", telegram_user_id);
 
-        self.clean_up_plays(telegram_user_id)?;
+        self.abandon_pending_play(telegram_user_id)?;
+        self.finish_mistakes_session(chat_id, telegram_user_id)?;
+        self.send_session_summary(chat_id, telegram_user_id)?;
 
-        let text = "Stopping the game for now.\n\
-        Send /help for further information\
-        ";
-        self.telegram_client.send_message(chat_id, text)?;
+        let user: User = self.get_user(telegram_user_id)?;
+        let text: String = i18n::text(user.settings.ui_language, "stop");
+        self.chat_client.send_message(chat_id, &text)?;
         Ok(())
     }
 
+    fn send_session_summary(
+        &mut self,
+        chat_id: i64,
+        telegram_user_id: u64,
+    ) -> Result<(), Box<dyn Error>> {
+        let user: User = self.get_user(telegram_user_id)?;
+        let Some(session) = self.end_session(user.id)? else {
+            return Ok(());
+        };
+        let plays: Vec<UserPlay> = self
+            .user_plays_repo
+            .get_answered_since(user.id, session.first_play_id)?;
+        let mut missed_noun_ids: Vec<i32> = Vec::new();
+        for play in plays.iter().filter(|play| play.answer == Some(false)) {
+            if !missed_noun_ids.contains(&play.noun_id) {
+                missed_noun_ids.push(play.noun_id);
+            }
+        }
+        let missed: Vec<Noun> = missed_noun_ids
+            .into_iter()
+            .map(|noun_id| self.nouns_repo.get(noun_id))
+            .collect::<Result<_, _>>()?;
+        let text: String = sessions::summary(&plays, &missed, user.settings.ui_language);
+        self.chat_client.send_message(chat_id, &text)
+    }
+
+    /// Closes the user's open session, returning it unless it had already
+    /// timed out.
+    fn end_session(&mut self, user_id: i32) -> Result<Option<Session>, Box<dyn Error>> {
+        let now: SystemTime = SystemTime::now();
+        let Some(session) = self.sessions_repo.get_open(user_id)? else {
+            return Ok(None);
+        };
+        if sessions::is_expired(&session, now) {
+            self.sessions_repo.end(session.id, session.last_active_at)?;
+            return Ok(None);
+        }
+        self.sessions_repo.end(session.id, now)?;
+        Ok(Some(session))
+    }
+
+    /// Keeps the user's session going, or opens one with this answer after
+    /// /start, /stop or a timeout.
+    fn track_session(&mut self, play: &UserPlay) -> Result<(), Box<dyn Error>> {
+        let now: SystemTime = SystemTime::now();
+        match self.sessions_repo.get_open(play.user_id)? {
+            Some(session) if !sessions::is_expired(&session, now) => {
+                self.sessions_repo.touch(session.id, now)
+            }
+            expired => {
+                if let Some(session) = expired {
+                    self.sessions_repo.end(session.id, session.last_active_at)?;
+                }
+                self.sessions_repo
+                    .insert(play.user_id, play.id, now)
+                    .map(|_| ())
+            }
+        }
+    }
+
     fn send_next_guess(
         &mut self,
         chat_id: i64,
         telegram_user_id: u64,
     ) -> Result<(), Box<dyn Error>> {
-        self.clean_up_plays(telegram_user_id)?;
-        let noun: Noun = self.nouns_repo.get_random()?;
+        self.abandon_pending_play(telegram_user_id)?;
         let user: User = self.users_repo.get(telegram_user_id)?.unwrap();
-        self.user_plays_repo.insert(user.id, noun.id)?;
+        let user: User = self.promote_if_ready(chat_id, user)?;
+        let next_mistake: Option<Option<i32>> =
+            self.update_mistakes_session(user.id, MistakesSession::next_noun_id)?;
+        let noun: Noun = match next_mistake {
+            Some(Some(noun_id)) => self.nouns_repo.get(noun_id)?,
+            Some(None) => return self.complete_mistakes_session(chat_id, &user),
+            None => self.pick_next_noun(
+                user.id,
+                user.settings.language,
+                user.settings.difficulty.max_rank(),
+            )?,
+        };
+        let exercise_type: ExerciseType = Self::pick_exercise_type(user.settings.quiz_mode, &noun);
+        let play_id: i32 = self
+            .user_plays_repo
+            .insert(user.id, noun.id, exercise_type)?;
+
+        let lang: UiLanguage = user.settings.ui_language;
+        let (message_text, choices) = match exercise_type {
+            ExerciseType::Gender => (
+                i18n::format(
+                    lang,
+                    "question-gender",
+                    &[("word", &noun.word), ("english", &noun.english)],
+                ),
+                Self::build_answer_choices(play_id, noun.language, lang),
+            ),
+            ExerciseType::SingularArticle | ExerciseType::PluralArticle => {
+                Self::build_article_question(play_id, &noun, exercise_type, lang)
+            }
+        };
+        let rows: Vec<Vec<Choice>> = vec![choices, Self::build_action_choices(play_id, lang)];
+        self.chat_client.send_menu(chat_id, &message_text, &rows)
+    }
+
+    /// Article questions are only asked for Spanish nouns, and plural ones
+    /// only for nouns with a known plural form.
+    fn pick_exercise_type(quiz_mode: QuizMode, noun: &Noun) -> ExerciseType {
+        let mut rng = rand::thread_rng();
+        let asks_article: bool = match quiz_mode {
+            _ if noun.language != Language::Spanish => false,
+            QuizMode::Gender => false,
+            QuizMode::Article => true,
+            QuizMode::Mixed => rng.gen_bool(0.5),
+        };
+        if !asks_article {
+            ExerciseType::Gender
+        } else if noun.plural_form().is_some() && rng.gen_bool(0.5) {
+            ExerciseType::PluralArticle
+        } else {
+            ExerciseType::SingularArticle
+        }
+    }
+
+    /// Asks for either the definite or the indefinite article, offering all
+    /// four forms so the number has to be matched too.
+    fn build_article_question(
+        play_id: i32,
+        noun: &Noun,
+        exercise_type: ExerciseType,
+        ui_language: UiLanguage,
+    ) -> (String, Vec<Choice>) {
+        let (form, hint) = match (exercise_type, noun.plural_form()) {
+            (ExerciseType::PluralArticle, Some(plural)) => {
+                let hint: String = i18n::format(
+                    ui_language,
+                    "question-plural-hint",
+                    &[("english", &noun.english)],
+                );
+                (plural, hint)
+            }
+            _ => (noun.word.clone(), noun.english.clone()),
+        };
+        let articles: [Article; 4] = if rand::thread_rng().gen_bool(0.5) {
+            Article::DEFINITE
+        } else {
+            Article::INDEFINITE
+        };
+        let text: String = i18n::format(
+            ui_language,
+            "question-article",
+            &[("word", &form), ("english", &hint)],
+        );
+        (text, Self::build_article_choices(play_id, &articles))
+    }
+
+    fn complete_mistakes_session(
+        &mut self,
+        chat_id: i64,
+        user: &User,
+    ) -> Result<(), Box<dyn Error>> {
+        let Some(session) = self.take_mistakes_session(user.id)? else {
+            return Ok(());
+        };
+        let lang: UiLanguage = user.settings.ui_language;
+        let summary: String = session.summary(lang);
+        let text: String = i18n::format(lang, "mistakes-completed", &[("summary", &summary)]);
+        self.chat_client.send_message(chat_id, &text)
+    }
+
+    /// Nouns of the language due for review come back whatever their rank;
+    /// new ones are only picked within the user's level.
+    fn pick_next_noun(
+        &mut self,
+        user_id: i32,
+        language: Language,
+        max_rank: Option<u32>,
+    ) -> Result<Noun, Box<dyn Error>> {
+        if let Some(noun) = self.nouns_repo.get_most_overdue(user_id, language)? {
+            return Ok(noun);
+        }
+        if let Some(noun) = self
+            .nouns_repo
+            .get_random_unseen(user_id, language, max_rank)?
+        {
+            return Ok(noun);
+        }
+        self.nouns_repo.get_random(language, max_rank)
+    }
 
-        let message_text = format!(
-            "What's the gender of '{}' ({})?",
-            noun.spanish, noun.english
+    /// Moves the user up a level once their latest answers on the nouns their
+    /// level introduced are accurate enough.
+    fn promote_if_ready(&mut self, chat_id: i64, user: User) -> Result<User, Box<dyn Error>> {
+        let difficulty = user.settings.difficulty;
+        let Some(next_level) = difficulty.next_level() else {
+            return Ok(user);
+        };
+        let recent: AnswerCount = self.user_plays_repo.count_recent_answers_by_rank(
+            user.id,
+            difficulty.min_rank(),
+            difficulty.max_rank(),
+            PROMOTION_WINDOW,
+        )?;
+        if recent.answered < PROMOTION_WINDOW || recent.accuracy() < PROMOTION_ACCURACY {
+            return Ok(user);
+        }
+
+        let settings = UserSettings {
+            difficulty: next_level,
+            ..user.settings
+        };
+        self.users_repo.update_settings(user.id, &settings)?;
+        let lang: UiLanguage = settings.ui_language;
+        let text: String = i18n::format(
+            lang,
+            "level-up",
+            &[
+                ("level", &i18n::difficulty(lang, next_level)),
+                ("scope", &i18n::scope(lang, next_level)),
+            ],
         );
-        self.telegram_client.send_question(chat_id, &message_text)?;
-        Ok(())
+        self.chat_client.send_message(chat_id, &text)?;
+        Ok(User { settings, ..user })
+    }
+
+    fn schedule_review(
+        &mut self,
+        user_id: i32,
+        noun_id: i32,
+        is_correct: bool,
+    ) -> Result<(), Box<dyn Error>> {
+        let previous: Option<NounReview> = self.noun_reviews_repo.get(user_id, noun_id)?;
+        let review: NounReview =
+            spaced_repetition::review(previous, user_id, noun_id, is_correct, SystemTime::now());
+        self.noun_reviews_repo.upsert(&review)
     }
 
-    fn send_welcome_message(&mut self, chat_id: i64) -> Result<(), Box<dyn Error>> {
-        let welcome_message = "\
-        Welcome to 'El la la' game.\n\n\
-        Your knowledge on Spanish nouns' gender is going to be tested.\n\
-        Type /help for further information.\
-        ";
-        self.telegram_client
-            .send_message(chat_id, welcome_message)?;
+    fn send_welcome_message(
+        &mut self,
+        chat_id: i64,
+        settings: &UserSettings,
+    ) -> Result<(), Box<dyn Error>> {
+        let learning: String = i18n::learning_language(settings.ui_language, settings.language);
+        let welcome_message: String =
+            i18n::format(settings.ui_language, "welcome", &[("language", &learning)]);
+        self.chat_client.send_message(chat_id, &welcome_message)?;
         Ok(())
     }
 
-    fn ensure_user_exists(&mut self, telegram_user_id: u64) -> Result<(), Box<dyn Error>> {
+    /// New users are talked to in the language of their app when it is
+    /// supported, and in English otherwise.
+    fn ensure_user_exists(
+        &mut self,
+        telegram_user_id: u64,
+        language_code: Option<&str>,
+    ) -> Result<(), Box<dyn Error>> {
         let user: Option<User> = self.users_repo.get(telegram_user_id)?;
 
         if user.is_none() {
+            let defaults = UserSettings::default();
             let new_user = User {
                 id: 0,
                 telegram_user_id,
+                settings: UserSettings {
+                    ui_language: language_code
+                        .and_then(UiLanguage::from_language_code)
+                        .unwrap_or(defaults.ui_language),
+                    ..defaults
+                },
             };
             self.users_repo.insert(&new_user)?;
         }
         Ok(())
     }
 
-    fn clean_up_plays(&mut self, telegram_user_id: u64) -> Result<(), Box<dyn Error>> {
-        self.get_user(telegram_user_id)
-            .and_then(|user| self.remove_user_last_play(user.id))
-    }
-
-    fn remove_user_last_play(&mut self, user_id: i32) -> Result<(), Box<dyn Error>> {
-        self.user_plays_repo
-            .get_last(user_id)
-            .and_then(|maybe_user| match maybe_user {
-                Some(user_play) => self.user_plays_repo.remove(user_play.id),
-                None => Ok(()),
-            })
+    /// Ends the question left unanswered, if any, keeping it on record so
+    /// abandoned questions show up next to skipped ones.
+    fn abandon_pending_play(&mut self, telegram_user_id: u64) -> Result<(), Box<dyn Error>> {
+        let user: User = self.get_user(telegram_user_id)?;
+        let Some(pending_play) = self.user_plays_repo.get_last(user.id)? else {
+            return Ok(());
+        };
+        let response = PlayResponse {
+            outcome: PlayOutcome::Abandoned,
+            chosen_answer: None,
+            answer_text: None,
+        };
+        self.user_plays_repo.update(pending_play.id, &response)
     }
 
     fn get_user(&mut self, telegram_user_id: u64) -> Result<User, Box<dyn Error>> {
@@ -151,16 +1108,20 @@ impl BotService {
             })
     }
 
-    fn handle_help_command(&self, chat_id: i64) -> Result<(), Box<dyn Error>> {
-        let text: &str = "\
-            /start -> Play the game\n\
-            /stop -> Stop the game\n\
-            /stats -> Check your current playing statistics\n\
-            ";
-        self.telegram_client
-            .send_message(chat_id, text)
-            .map(|_| ())
-            .map_err(|e| e.into())
+    fn handle_help_command(
+        &mut self,
+        chat_id: i64,
+        telegram_user_id: u64,
+    ) -> Result<(), Box<dyn Error>> {
+        let user: User = self.get_user(telegram_user_id)?;
+        let UserSettings {
+            ui_language,
+            language,
+            ..
+        } = user.settings;
+        let articles: String = Self::gender_article_list(language, ui_language);
+        let text: String = i18n::format(ui_language, "help", &[("articles", &articles)]);
+        self.chat_client.send_message(chat_id, &text)
     }
 
     fn handle_text_answer(
@@ -170,29 +1131,189 @@ impl BotService {
         telegram_user_id: u64,
         message_id: i32,
     ) -> Result<(), Box<dyn Error>> {
-        self.handle_current_guess(text_answer, chat_id, telegram_user_id, message_id)?;
+        let current_play: UserPlay = self.get_current_play(telegram_user_id)?;
+        let language: Language = self.nouns_repo.get(current_play.noun_id)?.language;
+        let ui_language: UiLanguage = self.get_user(telegram_user_id)?.settings.ui_language;
+        let Some(answer) = answer_parser::parse(text_answer, current_play.exercise_type, language)
+        else {
+            return self.send_unrecognized_answer(
+                chat_id,
+                message_id,
+                text_answer,
+                current_play.exercise_type,
+                language,
+                ui_language,
+            );
+        };
+        self.handle_current_guess(
+            &current_play,
+            &answer,
+            text_answer.trim(),
+            chat_id,
+            telegram_user_id,
+            message_id,
+        )?;
         self.send_next_guess(chat_id, telegram_user_id)?;
         Ok(())
     }
 
+    /// Articles answering the gender questions of the language, such as
+    /// "el, la or el/la".
+    fn gender_article_list(language: Language, ui_language: UiLanguage) -> String {
+        let articles: Vec<&str> = language
+            .answer_genders()
+            .iter()
+            .map(|gender| language.definite_article(*gender))
+            .collect();
+        i18n::format(
+            ui_language,
+            "list-or",
+            &[
+                ("head", &articles[..articles.len() - 1].join(", ")),
+                ("last", &articles[articles.len() - 1]),
+            ],
+        )
+    }
+
+    /// Unrecognised text is not graded, so a typo doesn't count as a mistake.
+    fn send_unrecognized_answer(
+        &self,
+        chat_id: i64,
+        message_id: i32,
+        text_answer: &str,
+        exercise_type: ExerciseType,
+        language: Language,
+        ui_language: UiLanguage,
+    ) -> Result<(), Box<dyn Error>> {
+        let expected: String = match exercise_type {
+            ExerciseType::Gender => {
+                let names: Vec<String> = language
+                    .answer_genders()
+                    .iter()
+                    .map(|gender| i18n::gender(ui_language, *gender).to_lowercase())
+                    .collect();
+                let articles: String = Self::gender_article_list(language, ui_language);
+                i18n::format(
+                    ui_language,
+                    "expected-gender",
+                    &[("articles", &articles), ("genders", &names.join(", "))],
+                )
+            }
+            ExerciseType::SingularArticle | ExerciseType::PluralArticle => {
+                i18n::text(ui_language, "expected-article")
+            }
+        };
+        let text: String = i18n::format(
+            ui_language,
+            "unrecognised-answer",
+            &[("answer", &text_answer.trim()), ("expected", &expected)],
+        );
+        self.chat_client.send_reply(chat_id, message_id, &text)
+    }
+
     fn handle_current_guess(
         &mut self,
-        text_answer: &str,
+        current_play: &UserPlay,
+        answer: &Answer,
+        answer_text: &str,
         chat_id: i64,
         telegram_user_id: u64,
         message_id: i32,
     ) -> Result<(), Box<dyn Error>> {
-        let current_play: UserPlay = self.get_current_play(telegram_user_id)?;
-        let playing_noun: Noun = self.nouns_repo.get(current_play.noun_id)?;
-        let is_correct_guess: bool = playing_noun.gender.is_match(text_answer);
-        self.user_plays_repo
-            .update(current_play.id, is_correct_guess)?;
+        let user: User = self.get_user(telegram_user_id)?;
+        let (playing_noun, is_correct_guess) =
+            self.grade_play(current_play, answer, Some(answer_text))?;
         let reacting_emoji: &str = if is_correct_guess {
             self.get_random_positive_reaction()
         } else {
             "💩"
         };
-        self.telegram_client.send_reaction(chat_id, message_id, reacting_emoji)
+        self.chat_client
+            .react(chat_id, message_id, reacting_emoji)?;
+
+        if !is_correct_guess && user.settings.show_correct_answer {
+            let lang: UiLanguage = user.settings.ui_language;
+            self.send_correct_answer(chat_id, message_id, &playing_noun, current_play, lang)?;
+        }
+        self.send_goal_progress(chat_id, &user)
+    }
+
+    /// Celebrates the answer reaching the daily goal.
+    fn send_goal_progress(&mut self, chat_id: i64, user: &User) -> Result<(), Box<dyn Error>> {
+        let streaks: Streaks = self.get_streaks(user)?;
+        if streaks.answered_today != user.settings.daily_goal {
+            return Ok(());
+        }
+        let text: String = streaks::goal_reached(&streaks, &user.settings);
+        self.chat_client.send_message(chat_id, &text)
+    }
+
+    fn get_streaks(&mut self, user: &User) -> Result<Streaks, Box<dyn Error>> {
+        let settings: &UserSettings = &user.settings;
+        let answers_by_day: Vec<(i64, u32)> = self
+            .user_plays_repo
+            .count_answers_by_local_day(user.id, settings.utc_offset_minutes)?;
+        let today: i64 = settings.local_day(SystemTime::now());
+        Ok(streaks::compute(&answers_by_day, today, settings))
+    }
+
+    /// Records the answer along with the text it was typed as, if any.
+    fn grade_play(
+        &mut self,
+        play: &UserPlay,
+        answer: &Answer,
+        answer_text: Option<&str>,
+    ) -> Result<(Noun, bool), Box<dyn Error>> {
+        let playing_noun: Noun = self.nouns_repo.get(play.noun_id)?;
+        let is_correct_guess: bool = playing_noun.is_correct_answer(play.exercise_type, answer);
+        let response = PlayResponse {
+            outcome: PlayOutcome::graded(is_correct_guess),
+            chosen_answer: Some(answer.clone()),
+            answer_text: answer_text.map(str::to_string),
+        };
+        self.user_plays_repo.update(play.id, &response)?;
+        self.track_session(play)?;
+        self.schedule_review(play.user_id, playing_noun.id, is_correct_guess)?;
+        self.update_mistakes_session(play.user_id, |session| {
+            session.record(&playing_noun, is_correct_guess)
+        })?;
+        Ok((playing_noun, is_correct_guess))
+    }
+
+    fn send_correct_answer(
+        &self,
+        chat_id: i64,
+        message_id: i32,
+        noun: &Noun,
+        play: &UserPlay,
+        ui_language: UiLanguage,
+    ) -> Result<(), Box<dyn Error>> {
+        let gender: String = i18n::gender_in_sentence(ui_language, noun.gender);
+        let plural: Option<String> = noun.with_plural_article();
+        let (key, noun_form): (&str, String) = match (play.exercise_type, &noun.gender, plural) {
+            (ExerciseType::PluralArticle, _, Some(plural)) => ("correct-answer", plural),
+            (_, Gender::Any, _) => ("correct-answer-any", noun.with_article()),
+            (_, Gender::Feminine, _) if noun.stressed_a => {
+                ("correct-answer-stressed-a", noun.with_article())
+            }
+            _ => ("correct-answer", noun.with_article()),
+        };
+        let text: String = i18n::format(
+            ui_language,
+            key,
+            &[
+                ("noun", &noun_form),
+                ("gender", &gender),
+                ("masculine", &noun.with_article_for(Gender::Masculine)),
+                ("feminine", &noun.with_article_for(Gender::Feminine)),
+            ],
+        );
+        let text: String = match gender_rules::explain(noun, ui_language) {
+            Some(explanation) => format!("{}\n{}", text, explanation),
+            None => text,
+        };
+        self.chat_client.send_reply(chat_id, message_id, &text)?;
+        Ok(())
     }
 
     fn get_random_positive_reaction(&self) -> &str {
@@ -0,0 +1,147 @@
+use crate::domain::{Gender, Language, Noun, UiLanguage};
+use crate::i18n;
+
+/// Nouns of Greek origin ending in -ma, masculine despite the final "a".
+const GREEK_MA_NOUNS: [&str; 30] = [
+    "aroma",
+    "axioma",
+    "carisma",
+    "clima",
+    "crucigrama",
+    "diagrama",
+    "dilema",
+    "diploma",
+    "dogma",
+    "drama",
+    "emblema",
+    "enigma",
+    "esquema",
+    "estigma",
+    "fantasma",
+    "idioma",
+    "lema",
+    "panorama",
+    "pijama",
+    "plasma",
+    "poema",
+    "problema",
+    "programa",
+    "prisma",
+    "síntoma",
+    "sistema",
+    "telegrama",
+    "tema",
+    "teorema",
+    "trauma",
+];
+
+/// Gender usually taken by nouns with a given ending.
+#[derive(Debug)]
+pub struct GenderRule {
+    pub gender: Gender,
+    /// Catalogue key of the sentence stating the rule.
+    pub description_key: &'static str,
+    applies: fn(&str) -> bool,
+}
+
+/// Rules by priority: the first one matching a noun is the one it follows or
+/// breaks, so longer endings come before the shorter ones they end with.
+static RULES: [GenderRule; 8] = [
+    GenderRule {
+        gender: Gender::Feminine,
+        description_key: "rule-cion",
+        applies: |word| word.ends_with("ción") || word.ends_with("sión") || word.ends_with("xión"),
+    },
+    GenderRule {
+        gender: Gender::Feminine,
+        description_key: "rule-dad",
+        applies: |word| word.ends_with("dad") || word.ends_with("tad") || word.ends_with("tud"),
+    },
+    GenderRule {
+        gender: Gender::Feminine,
+        description_key: "rule-umbre",
+        applies: |word| word.ends_with("umbre"),
+    },
+    GenderRule {
+        gender: Gender::Masculine,
+        description_key: "rule-greek-ma",
+        applies: |word| GREEK_MA_NOUNS.contains(&word),
+    },
+    GenderRule {
+        gender: Gender::Masculine,
+        description_key: "rule-aje",
+        applies: |word| word.ends_with("aje"),
+    },
+    GenderRule {
+        gender: Gender::Masculine,
+        description_key: "rule-or",
+        applies: |word| word.ends_with("or"),
+    },
+    GenderRule {
+        gender: Gender::Masculine,
+        description_key: "rule-o",
+        applies: |word| word.ends_with('o'),
+    },
+    GenderRule {
+        gender: Gender::Feminine,
+        description_key: "rule-a",
+        applies: |word| word.ends_with('a'),
+    },
+];
+
+#[derive(Debug)]
+pub enum Classification {
+    Follows(&'static GenderRule),
+    Exception(&'static GenderRule),
+    NoRule,
+}
+
+/// Classifies a noun against the ending rules, looking at its first word so
+/// "fin de semana" is judged by "fin".
+pub fn classify(noun: &Noun) -> Classification {
+    let word: String = head_word(&noun.word);
+    let Some(rule) = RULES.iter().find(|rule| (rule.applies)(&word)) else {
+        return Classification::NoRule;
+    };
+    if rule.gender == noun.gender {
+        Classification::Follows(rule)
+    } else {
+        Classification::Exception(rule)
+    }
+}
+
+/// One sentence saying why the noun has its gender. The rules only cover
+/// Spanish, so nouns of other languages have none.
+pub fn explain(noun: &Noun, ui_language: UiLanguage) -> Option<String> {
+    if noun.language != Language::Spanish {
+        return None;
+    }
+    let noun_with_article: String = noun.with_article();
+    let explain_with = |key: &str, rule: &GenderRule| {
+        let description: String = i18n::text(ui_language, rule.description_key);
+        i18n::format(
+            ui_language,
+            key,
+            &[("noun", &noun_with_article), ("rule", &description)],
+        )
+    };
+    let explanation: String = match (classify(noun), &noun.gender) {
+        (Classification::Follows(rule), _) => explain_with("explain-follows", rule),
+        (Classification::Exception(rule), Gender::Any) => explain_with("explain-either", rule),
+        (Classification::Exception(rule), _) => explain_with("explain-exception", rule),
+        (Classification::NoRule, _) => i18n::format(
+            ui_language,
+            "explain-no-rule",
+            &[("noun", &noun_with_article)],
+        ),
+    };
+    Some(explanation)
+}
+
+/// First word of a noun, lowercased, which its ending is read from.
+pub fn head_word(word: &str) -> String {
+    word.split(|c: char| c.is_whitespace() || c == ',' || c == '(')
+        .next()
+        .unwrap_or_default()
+        .to_lowercase()
+}
@@ -0,0 +1,64 @@
+use crate::domain::{AnswerCount, MistakesSession, Noun, UiLanguage};
+use crate::i18n;
+
+/// Correct answers needed since the last mistake for a noun to be mastered.
+pub const REQUIRED_CORRECT_ANSWERS: u32 = 3;
+
+impl MistakesSession {
+    pub fn new(noun_ids: Vec<i32>) -> Self {
+        MistakesSession {
+            queue: noun_ids.into(),
+            count: AnswerCount::default(),
+            still_missed: Vec::new(),
+        }
+    }
+
+    pub fn next_noun_id(&mut self) -> Option<i32> {
+        self.queue.pop_front()
+    }
+
+    /// Puts a noun the user couldn't answer back at the end of the queue.
+    pub fn postpone(&mut self, noun_id: i32) {
+        self.queue.push_back(noun_id);
+    }
+
+    pub fn record(&mut self, noun: &Noun, is_correct: bool) {
+        self.count.answered += 1;
+        let noun_with_article: String = noun.with_article();
+        if is_correct {
+            self.count.correct += 1;
+            self.still_missed
+                .retain(|missed| *missed != noun_with_article);
+        } else {
+            self.queue.push_back(noun.id);
+            if !self.still_missed.contains(&noun_with_article) {
+                self.still_missed.push(noun_with_article);
+            }
+        }
+    }
+
+    pub fn summary(&self, ui_language: UiLanguage) -> String {
+        if self.count.answered == 0 {
+            return i18n::text(ui_language, "mistakes-stopped");
+        }
+        let still_missed: String = if self.still_missed.is_empty() {
+            i18n::text(ui_language, "mistakes-all-right")
+        } else {
+            i18n::format(
+                ui_language,
+                "mistakes-still-missed",
+                &[("nouns", &self.still_missed.join(", "))],
+            )
+        };
+        i18n::format(
+            ui_language,
+            "mistakes-summary",
+            &[
+                ("answered", &self.count.answered),
+                ("correct", &self.count.correct),
+                ("accuracy", &format!("{:.0}", self.count.accuracy())),
+                ("still_missed", &still_missed),
+            ],
+        )
+    }
+}
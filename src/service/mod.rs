@@ -1 +1,12 @@
+pub mod answer_parser;
 pub mod bot;
+pub mod gender_rules;
+pub mod mistakes;
+pub mod noun_difficulty;
+pub mod reminders;
+pub mod scheduler;
+pub mod sessions;
+pub mod settings;
+pub mod spaced_repetition;
+pub mod streaks;
+pub mod weakness;
@@ -0,0 +1,68 @@
+use crate::domain::{AnswerCount, Noun, NounDifficulty, UiLanguage};
+use crate::i18n;
+use std::time::Duration;
+
+/// How often the answers of all users are summed up again.
+pub const REFRESH_INTERVAL: Duration = Duration::from_secs(60 * 60);
+/// Answers a noun needs before it can be rated among the hardest or easiest.
+pub const MIN_ANSWERS: u32 = 5;
+/// Nouns listed on each side of the report unless asked otherwise.
+pub const DEFAULT_REPORT_SIZE: u32 = 10;
+
+/// Number of nouns asked for after a command or subcommand, such as the "20"
+/// of "/hardest 20".
+pub fn parse_report_size(argument: Option<&str>) -> Result<u32, String> {
+    match argument
+        .map(str::trim)
+        .filter(|argument| !argument.is_empty())
+    {
+        None => Ok(DEFAULT_REPORT_SIZE),
+        Some(argument) => argument
+            .parse::<u32>()
+            .ok()
+            .filter(|size| *size > 0)
+            .ok_or_else(|| format!("Invalid number of nouns: {}", argument)),
+    }
+}
+
+/// Report of the nouns all users answer least and most accurately.
+pub fn report(
+    ui_language: UiLanguage,
+    hardest: &[(Noun, NounDifficulty)],
+    easiest: &[(Noun, NounDifficulty)],
+) -> String {
+    if hardest.is_empty() {
+        return i18n::format(ui_language, "hardest-none", &[("answers", &MIN_ANSWERS)]);
+    }
+    let sections: Vec<String> = [("hardest-title", hardest), ("easiest-title", easiest)]
+        .iter()
+        .map(|(key, nouns)| {
+            let title: String = i18n::format(ui_language, key, &[("answers", &MIN_ANSWERS)]);
+            let rows: Vec<String> = nouns
+                .iter()
+                .enumerate()
+                .map(|(index, (noun, difficulty))| format_row(index + 1, noun, difficulty))
+                .collect();
+            format!("{}\n{}", title, rows.join("\n"))
+        })
+        .collect();
+    sections.join("\n\n")
+}
+
+/// Row such as "1. la mano (es): 40% (2/5), 3.2 s".
+fn format_row(position: usize, noun: &Noun, difficulty: &NounDifficulty) -> String {
+    let count: &AnswerCount = &difficulty.count;
+    let mut row: String = format!(
+        "{}. {} ({}): {:.0}% ({}/{})",
+        position,
+        noun.with_article(),
+        noun.language.as_str(),
+        count.accuracy(),
+        count.correct,
+        count.answered
+    );
+    if let Some(mean_latency_ms) = difficulty.mean_latency_ms {
+        row.push_str(&format!(", {:.1} s", f64::from(mean_latency_ms) / 1000.0));
+    }
+    row
+}
@@ -0,0 +1,38 @@
+use crate::client::chat::Choice;
+use crate::domain::{ReminderCandidate, UserSettings};
+use crate::i18n;
+use std::time::SystemTime;
+
+pub const PLAY_NOW_DATA: &str = "reminder:play";
+pub const TURN_OFF_DATA: &str = "reminder:off";
+
+/// A user is reminded once a day, after their reminder time and only if they
+/// haven't answered anything yet that day, both in their timezone.
+pub fn is_due(candidate: &ReminderCandidate, now: SystemTime) -> bool {
+    let settings: &UserSettings = &candidate.user.settings;
+    let Some(reminder_time) = settings.reminder_time else {
+        return false;
+    };
+    let today: i64 = settings.local_day(now);
+    let is_today =
+        |time: Option<SystemTime>| time.is_some_and(|time| settings.local_day(time) == today);
+    settings.local_minute(now) >= reminder_time
+        && !is_today(candidate.last_reminded_at)
+        && !is_today(candidate.last_answered_at)
+}
+
+pub fn message(settings: &UserSettings) -> (String, Vec<Vec<Choice>>) {
+    let lang = settings.ui_language;
+    let text: String = i18n::format(lang, "reminder", &[("goal", &settings.daily_goal)]);
+    let rows: Vec<Vec<Choice>> = vec![
+        vec![Choice::new(
+            &i18n::text(lang, "reminder-play"),
+            PLAY_NOW_DATA.to_string(),
+        )],
+        vec![Choice::new(
+            &i18n::text(lang, "reminder-stop"),
+            TURN_OFF_DATA.to_string(),
+        )],
+    ];
+    (text, rows)
+}
@@ -0,0 +1,37 @@
+use crate::client::chat::ChatEvent;
+use crate::repository::analytics::AnalyticsRepositoryTrait;
+use std::sync::mpsc::Sender;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+/// Time between two ticks. Reminders are set to the minute, so they go out at
+/// most this late.
+pub const TICK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Starts a thread feeding a [`ChatEvent::Tick`] to the bot every `interval`,
+/// until the bot stops receiving events.
+pub fn spawn(event_sender: Sender<ChatEvent>, interval: Duration) {
+    thread::spawn(move || loop {
+        thread::sleep(interval);
+        if let Err(err) = event_sender.send(ChatEvent::Tick(SystemTime::now())) {
+            eprintln!("Failed to send tick: {}", err);
+            return;
+        }
+    });
+}
+
+/// Starts a thread refreshing the noun difficulty right away and then every
+/// `interval`. The aggregate reads every play, so it runs apart from the bot,
+/// on connections of its own, for users not to wait on it.
+pub fn spawn_noun_difficulty_refresh<R>(mut analytics_repo: R, interval: Duration)
+where
+    R: AnalyticsRepositoryTrait + Send + 'static,
+{
+    thread::spawn(move || loop {
+        analytics_repo
+            .refresh_noun_difficulty(SystemTime::now())
+            .inspect_err(|e| eprintln!("Failed to refresh the noun difficulty: {}", e))
+            .ok();
+        thread::sleep(interval);
+    });
+}
@@ -0,0 +1,48 @@
+use crate::domain::{AnswerCount, Noun, Session, UiLanguage, UserPlay};
+use crate::i18n;
+use std::time::{Duration, SystemTime};
+
+/// Time without an answer after which a session is over.
+pub const INACTIVITY_TIMEOUT: Duration = Duration::from_secs(30 * 60);
+
+pub fn is_expired(session: &Session, now: SystemTime) -> bool {
+    now.duration_since(session.last_active_at)
+        .is_ok_and(|idle| idle > INACTIVITY_TIMEOUT)
+}
+
+/// Summary of the plays answered in a session and the nouns missed in it.
+pub fn summary(plays: &[UserPlay], missed: &[Noun], ui_language: UiLanguage) -> String {
+    let mut count = AnswerCount::default();
+    let mut run: u32 = 0;
+    let mut best_run: u32 = 0;
+    for play in plays {
+        count.answered += 1;
+        if play.answer == Some(true) {
+            count.correct += 1;
+            run += 1;
+            best_run = best_run.max(run);
+        } else {
+            run = 0;
+        }
+    }
+    let missed: String = if missed.is_empty() {
+        i18n::text(ui_language, "session-missed-none")
+    } else {
+        missed
+            .iter()
+            .map(Noun::with_article)
+            .collect::<Vec<String>>()
+            .join(", ")
+    };
+    i18n::format(
+        ui_language,
+        "session-summary",
+        &[
+            ("answered", &count.answered),
+            ("correct", &count.correct),
+            ("accuracy", &format!("{:.0}", count.accuracy())),
+            ("best", &best_run),
+            ("missed", &missed),
+        ],
+    )
+}
@@ -0,0 +1,226 @@
+use crate::client::chat::Choice;
+use crate::domain::{Difficulty, Language, QuizMode, UiLanguage, UserSettings};
+use crate::i18n;
+use std::str::FromStr;
+
+const DAILY_GOALS: [u32; 5] = [10, 20, 30, 50, 100];
+const REMINDER_TIMES: [Option<u16>; 5] = [
+    None,
+    Some(9 * 60),
+    Some(13 * 60),
+    Some(19 * 60),
+    Some(21 * 60),
+];
+const MIN_UTC_OFFSET_MINUTES: i32 = -12 * 60;
+const MAX_UTC_OFFSET_MINUTES: i32 = 14 * 60;
+
+/// Option picked on the settings menu. Choices carry it as `settings:<action>`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SettingsAction {
+    Language,
+    QuizMode,
+    ShowCorrectAnswer,
+    DailyGoal,
+    StreakFreeze,
+    Reminder,
+    TimezoneEarlier,
+    /// Back to UTC, from the button showing the current timezone.
+    TimezoneReset,
+    TimezoneLater,
+    UiLanguage,
+    Difficulty,
+    Done,
+}
+
+impl SettingsAction {
+    pub fn as_str(&self) -> &str {
+        match self {
+            SettingsAction::Language => "language",
+            SettingsAction::QuizMode => "quiz_mode",
+            SettingsAction::ShowCorrectAnswer => "show_correct_answer",
+            SettingsAction::DailyGoal => "daily_goal",
+            SettingsAction::StreakFreeze => "streak_freeze",
+            SettingsAction::Reminder => "reminder",
+            SettingsAction::TimezoneEarlier => "timezone_earlier",
+            SettingsAction::TimezoneReset => "timezone_reset",
+            SettingsAction::TimezoneLater => "timezone_later",
+            SettingsAction::UiLanguage => "ui_language",
+            SettingsAction::Difficulty => "difficulty",
+            SettingsAction::Done => "done",
+        }
+    }
+
+    fn choice(self, label: &str) -> Choice {
+        Choice::new(label, format!("settings:{}", self.as_str()))
+    }
+}
+
+impl FromStr for SettingsAction {
+    type Err = String;
+
+    fn from_str(action_str: &str) -> Result<Self, Self::Err> {
+        match action_str {
+            "language" => Ok(SettingsAction::Language),
+            "quiz_mode" => Ok(SettingsAction::QuizMode),
+            "show_correct_answer" => Ok(SettingsAction::ShowCorrectAnswer),
+            "daily_goal" => Ok(SettingsAction::DailyGoal),
+            "streak_freeze" => Ok(SettingsAction::StreakFreeze),
+            "reminder" => Ok(SettingsAction::Reminder),
+            "timezone_earlier" => Ok(SettingsAction::TimezoneEarlier),
+            "timezone_reset" => Ok(SettingsAction::TimezoneReset),
+            "timezone_later" => Ok(SettingsAction::TimezoneLater),
+            "ui_language" => Ok(SettingsAction::UiLanguage),
+            "difficulty" => Ok(SettingsAction::Difficulty),
+            "done" => Ok(SettingsAction::Done),
+            _ => Err(format!("Invalid settings action '{}'", action_str)),
+        }
+    }
+}
+
+/// Returns the action of a settings menu choice, or `None` for other choices.
+pub fn parse_action(data: &str) -> Option<Result<SettingsAction, String>> {
+    data.strip_prefix("settings:").map(str::parse)
+}
+
+/// Settings after the option was picked: lists cycle to their next value and
+/// the timezone moves by one hour.
+pub fn apply(settings: &UserSettings, action: SettingsAction) -> UserSettings {
+    let mut updated: UserSettings = settings.clone();
+    match action {
+        SettingsAction::Language => updated.language = next(&Language::ALL, settings.language),
+        SettingsAction::QuizMode => updated.quiz_mode = next(&QuizMode::ALL, settings.quiz_mode),
+        SettingsAction::ShowCorrectAnswer => {
+            updated.show_correct_answer = !settings.show_correct_answer
+        }
+        SettingsAction::DailyGoal => updated.daily_goal = next(&DAILY_GOALS, settings.daily_goal),
+        SettingsAction::StreakFreeze => updated.streak_freeze = !settings.streak_freeze,
+        SettingsAction::Reminder => {
+            updated.reminder_time = next(&REMINDER_TIMES, settings.reminder_time)
+        }
+        SettingsAction::TimezoneEarlier => {
+            updated.utc_offset_minutes =
+                (settings.utc_offset_minutes - 60).max(MIN_UTC_OFFSET_MINUTES)
+        }
+        SettingsAction::TimezoneReset => updated.utc_offset_minutes = 0,
+        SettingsAction::TimezoneLater => {
+            updated.utc_offset_minutes =
+                (settings.utc_offset_minutes + 60).min(MAX_UTC_OFFSET_MINUTES)
+        }
+        SettingsAction::UiLanguage => {
+            updated.ui_language = next(&UiLanguage::ALL, settings.ui_language)
+        }
+        SettingsAction::Difficulty => {
+            updated.difficulty = next(&Difficulty::ALL, settings.difficulty)
+        }
+        SettingsAction::Done => {}
+    }
+    updated
+}
+
+fn next<T: Copy + PartialEq>(values: &[T], current: T) -> T {
+    let index: usize = values
+        .iter()
+        .position(|value| *value == current)
+        .map_or(0, |index| (index + 1) % values.len());
+    values[index]
+}
+
+/// Menu text and rows of choices, each labelled with its current value.
+pub fn menu(settings: &UserSettings) -> (String, Vec<Vec<Choice>>) {
+    let lang: UiLanguage = settings.ui_language;
+    let text: String = i18n::text(lang, "settings-menu");
+    let rows: Vec<Vec<Choice>> = vec![
+        vec![SettingsAction::Language.choice(&i18n::format(
+            lang,
+            "settings-learning",
+            &[
+                ("flag", &settings.language.flag()),
+                (
+                    "language",
+                    &i18n::learning_language(lang, settings.language),
+                ),
+            ],
+        ))],
+        vec![SettingsAction::QuizMode.choice(&i18n::format(
+            lang,
+            "settings-quiz-mode",
+            &[("mode", &i18n::quiz_mode(lang, settings.quiz_mode))],
+        ))],
+        vec![SettingsAction::ShowCorrectAnswer.choice(&i18n::format(
+            lang,
+            "settings-show-correct-answer",
+            &[("value", &i18n::on_off(lang, settings.show_correct_answer))],
+        ))],
+        vec![SettingsAction::DailyGoal.choice(&i18n::format(
+            lang,
+            "settings-daily-goal",
+            &[("goal", &settings.daily_goal)],
+        ))],
+        vec![SettingsAction::StreakFreeze.choice(&i18n::format(
+            lang,
+            "settings-streak-freeze",
+            &[("value", &i18n::on_off(lang, settings.streak_freeze))],
+        ))],
+        vec![SettingsAction::Reminder.choice(&i18n::format(
+            lang,
+            "settings-reminder",
+            &[("time", &reminder(settings))],
+        ))],
+        vec![
+            SettingsAction::TimezoneEarlier.choice("−1h"),
+            SettingsAction::TimezoneReset.choice(&i18n::format(
+                lang,
+                "settings-timezone",
+                &[("offset", &settings.utc_offset())],
+            )),
+            SettingsAction::TimezoneLater.choice("+1h"),
+        ],
+        vec![SettingsAction::UiLanguage.choice(&i18n::format(
+            lang,
+            "settings-ui-language",
+            &[("language", &settings.ui_language)],
+        ))],
+        vec![SettingsAction::Difficulty.choice(&i18n::format(
+            lang,
+            "settings-difficulty",
+            &[
+                ("level", &i18n::difficulty(lang, settings.difficulty)),
+                ("scope", &i18n::scope(lang, settings.difficulty)),
+            ],
+        ))],
+        vec![SettingsAction::Done.choice(&i18n::text(lang, "settings-done"))],
+    ];
+    (text, rows)
+}
+
+pub fn summary(settings: &UserSettings) -> String {
+    let lang: UiLanguage = settings.ui_language;
+    i18n::format(
+        lang,
+        "settings-saved",
+        &[
+            (
+                "learning",
+                &i18n::learning_language(lang, settings.language),
+            ),
+            ("mode", &i18n::quiz_mode(lang, settings.quiz_mode)),
+            (
+                "show_correct_answer",
+                &i18n::on_off(lang, settings.show_correct_answer),
+            ),
+            ("goal", &settings.daily_goal),
+            ("streak_freeze", &i18n::on_off(lang, settings.streak_freeze)),
+            ("reminder", &reminder(settings)),
+            ("offset", &settings.utc_offset()),
+            ("language", &settings.ui_language),
+            ("level", &i18n::difficulty(lang, settings.difficulty)),
+            ("scope", &i18n::scope(lang, settings.difficulty)),
+        ],
+    )
+}
+
+fn reminder(settings: &UserSettings) -> String {
+    settings
+        .reminder()
+        .unwrap_or_else(|| i18n::on_off(settings.ui_language, false))
+}
@@ -0,0 +1,163 @@
+use crate::domain::NounReview;
+use std::time::{Duration, SystemTime};
+
+const INITIAL_EASE_FACTOR: f64 = 2.5;
+const MINIMUM_EASE_FACTOR: f64 = 1.3;
+const RELEARNING_INTERVAL_MINUTES: u32 = 10;
+const FIRST_INTERVAL_MINUTES: u32 = 24 * 60;
+const SECOND_INTERVAL_MINUTES: u32 = 6 * 24 * 60;
+const MAXIMUM_INTERVAL_MINUTES: u32 = 365 * 24 * 60;
+
+/// SM-2 answer quality (0-5) for a graded guess. A guess is either right or
+/// wrong, so only two grades are used: "correct with hesitation" and
+/// "incorrect, but remembered once seen".
+fn quality(is_correct: bool) -> f64 {
+    if is_correct {
+        4.0
+    } else {
+        1.0
+    }
+}
+
+/// Computes the next review state of a noun for a user following SM-2.
+/// Failed nouns are due again after a few minutes so they come back within
+/// the same playing session. A right guess before the noun is due, such as
+/// when it is drawn at random, keeps the schedule as it was, and intervals
+/// stop growing at a year.
+pub fn review(
+    previous: Option<NounReview>,
+    user_id: i32,
+    noun_id: i32,
+    is_correct: bool,
+    now: SystemTime,
+) -> NounReview {
+    if let Some(previous) = previous
+        .as_ref()
+        .filter(|review| is_correct && review.due_at > now)
+    {
+        return previous.clone();
+    }
+    let (repetitions, interval_minutes, ease_factor) = previous
+        .map(|review| {
+            (
+                review.repetitions,
+                review.interval_minutes,
+                review.ease_factor,
+            )
+        })
+        .unwrap_or((0, 0, INITIAL_EASE_FACTOR));
+
+    let q: f64 = quality(is_correct);
+    let ease_factor: f64 =
+        (ease_factor + (0.1 - (5.0 - q) * (0.08 + (5.0 - q) * 0.02))).max(MINIMUM_EASE_FACTOR);
+
+    let (repetitions, interval_minutes) = if !is_correct {
+        (0, RELEARNING_INTERVAL_MINUTES)
+    } else {
+        let interval_minutes: u32 = match repetitions {
+            0 => FIRST_INTERVAL_MINUTES,
+            1 => SECOND_INTERVAL_MINUTES,
+            _ => (f64::from(interval_minutes) * ease_factor).round() as u32,
+        };
+        (
+            repetitions + 1,
+            interval_minutes.min(MAXIMUM_INTERVAL_MINUTES),
+        )
+    };
+
+    NounReview {
+        user_id,
+        noun_id,
+        repetitions,
+        interval_minutes,
+        ease_factor,
+        due_at: now + Duration::from_secs(u64::from(interval_minutes) * 60),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn review_all(answers: &[bool]) -> NounReview {
+        let start: SystemTime = SystemTime::UNIX_EPOCH;
+        answers
+            .iter()
+            .fold(None, |previous: Option<NounReview>, is_correct| {
+                // Every answer comes once the previous review is due.
+                let now: SystemTime = previous.as_ref().map_or(start, |review| review.due_at);
+                Some(review(previous, 1, 2, *is_correct, now))
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn first_right_guess_is_due_in_a_day() {
+        let review: NounReview = review_all(&[true]);
+
+        assert_eq!(review.repetitions, 1);
+        assert_eq!(review.interval_minutes, FIRST_INTERVAL_MINUTES);
+        assert_eq!(review.ease_factor, INITIAL_EASE_FACTOR);
+    }
+
+    #[test]
+    fn wrong_guess_is_relearned_within_minutes() {
+        let review: NounReview = review_all(&[true, true, false]);
+
+        assert_eq!(review.repetitions, 0);
+        assert_eq!(review.interval_minutes, RELEARNING_INTERVAL_MINUTES);
+        assert!(review.ease_factor < INITIAL_EASE_FACTOR);
+    }
+
+    #[test]
+    fn repeated_right_guesses_grow_the_interval() {
+        let intervals: Vec<u32> = (1..=4)
+            .map(|count| review_all(&vec![true; count]).interval_minutes)
+            .collect();
+
+        assert_eq!(
+            intervals,
+            vec![
+                FIRST_INTERVAL_MINUTES,
+                SECOND_INTERVAL_MINUTES,
+                (f64::from(SECOND_INTERVAL_MINUTES) * INITIAL_EASE_FACTOR).round() as u32,
+                (f64::from(SECOND_INTERVAL_MINUTES) * INITIAL_EASE_FACTOR * INITIAL_EASE_FACTOR)
+                    .round() as u32,
+            ]
+        );
+    }
+
+    #[test]
+    fn interval_stops_growing_at_a_year() {
+        let review: NounReview = review_all(&[true; 50]);
+
+        assert_eq!(review.repetitions, 50);
+        assert_eq!(review.interval_minutes, MAXIMUM_INTERVAL_MINUTES);
+    }
+
+    #[test]
+    fn right_guess_before_due_keeps_the_schedule() {
+        let scheduled: NounReview = review_all(&[true, true]);
+        let early: SystemTime = scheduled.due_at - Duration::from_secs(60);
+
+        let review: NounReview = review(Some(scheduled.clone()), 1, 2, true, early);
+
+        assert_eq!(review.repetitions, scheduled.repetitions);
+        assert_eq!(review.interval_minutes, scheduled.interval_minutes);
+        assert_eq!(review.due_at, scheduled.due_at);
+    }
+
+    #[test]
+    fn wrong_guess_before_due_is_relearned() {
+        let scheduled: NounReview = review_all(&[true, true]);
+        let early: SystemTime = scheduled.due_at - Duration::from_secs(60);
+
+        let review: NounReview = review(Some(scheduled), 1, 2, false, early);
+
+        assert_eq!(review.repetitions, 0);
+        assert_eq!(
+            review.due_at,
+            early + Duration::from_secs(u64::from(RELEARNING_INTERVAL_MINUTES) * 60)
+        );
+    }
+}
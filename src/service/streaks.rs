@@ -0,0 +1,183 @@
+use crate::domain::{UiLanguage, UserSettings};
+use crate::i18n;
+
+/// Days apart two missed days have to be for the streak freeze to cover both.
+const FREEZE_PERIOD_DAYS: i64 = 7;
+
+/// Runs of consecutive days on which the daily goal was met.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Streaks {
+    pub current: u32,
+    pub best: u32,
+    pub answered_today: u32,
+}
+
+/// Streaks from the answers given each day, as returned by
+/// `count_answers_by_local_day`. Today doesn't break the current streak before
+/// it is over, and with the streak freeze one missed day a week is skipped.
+pub fn compute(answers_by_day: &[(i64, u32)], today: i64, settings: &UserSettings) -> Streaks {
+    let goal_days: Vec<i64> = answers_by_day
+        .iter()
+        .filter(|(_, answered)| *answered >= settings.daily_goal)
+        .map(|(day, _)| *day)
+        .collect();
+    let can_freeze = |last_frozen: Option<i64>, missed_day: i64| {
+        settings.streak_freeze
+            && last_frozen.map_or(true, |frozen| missed_day - frozen >= FREEZE_PERIOD_DAYS)
+    };
+
+    let mut run: u32 = 0;
+    let mut best: u32 = 0;
+    let mut previous: Option<i64> = None;
+    let mut last_frozen: Option<i64> = None;
+    for day in goal_days {
+        run = match previous {
+            Some(previous) if day - previous == 1 => run + 1,
+            Some(previous) if day - previous == 2 && can_freeze(last_frozen, previous + 1) => {
+                last_frozen = Some(previous + 1);
+                run + 1
+            }
+            _ => 1,
+        };
+        best = best.max(run);
+        previous = Some(day);
+    }
+
+    let is_running: bool = previous.is_some_and(|last| {
+        last >= today - 1 || (last == today - 2 && can_freeze(last_frozen, today - 1))
+    });
+    let answered_today: u32 = answers_by_day
+        .iter()
+        .find(|(day, _)| *day == today)
+        .map_or(0, |(_, answered)| *answered);
+    Streaks {
+        current: if is_running { run } else { 0 },
+        best,
+        answered_today,
+    }
+}
+
+pub fn goal_reached(streaks: &Streaks, settings: &UserSettings) -> String {
+    i18n::format(
+        settings.ui_language,
+        "goal-reached",
+        &[
+            ("answered", &streaks.answered_today),
+            ("goal", &settings.daily_goal),
+            ("streak", &streaks.current),
+        ],
+    )
+}
+
+pub fn summary(streaks: &Streaks, settings: &UserSettings) -> String {
+    let lang: UiLanguage = settings.ui_language;
+    let freeze: String = if settings.streak_freeze {
+        i18n::text(lang, "streak-freeze-on")
+    } else {
+        i18n::on_off(lang, false)
+    };
+    i18n::format(
+        lang,
+        "streak-summary",
+        &[
+            ("current", &days(lang, streaks.current)),
+            ("best", &days(lang, streaks.best)),
+            ("answered", &streaks.answered_today),
+            ("goal", &settings.daily_goal),
+            ("freeze", &freeze),
+        ],
+    )
+}
+
+fn days(lang: UiLanguage, count: u32) -> String {
+    i18n::plural(lang, "days", count, &[])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TODAY: i64 = 100;
+
+    fn settings(streak_freeze: bool) -> UserSettings {
+        UserSettings {
+            daily_goal: 5,
+            streak_freeze,
+            ..UserSettings::default()
+        }
+    }
+
+    /// Days relative to today on which the daily goal was met.
+    fn streaks(goal_days: &[i64], streak_freeze: bool) -> Streaks {
+        let answers_by_day: Vec<(i64, u32)> =
+            goal_days.iter().map(|day| (TODAY + day, 5)).collect();
+        compute(&answers_by_day, TODAY, &settings(streak_freeze))
+    }
+
+    #[test]
+    fn consecutive_days_make_a_streak() {
+        let streaks: Streaks = streaks(&[-3, -2, -1, 0], false);
+
+        assert_eq!((streaks.current, streaks.best), (4, 4));
+        assert_eq!(streaks.answered_today, 5);
+    }
+
+    #[test]
+    fn days_below_the_goal_do_not_count() {
+        let answers_by_day: Vec<(i64, u32)> = vec![(TODAY - 2, 5), (TODAY - 1, 4), (TODAY, 5)];
+
+        let streaks: Streaks = compute(&answers_by_day, TODAY, &settings(false));
+
+        assert_eq!((streaks.current, streaks.best), (1, 1));
+    }
+
+    #[test]
+    fn missed_day_breaks_the_streak_without_freeze() {
+        let streaks: Streaks = streaks(&[-4, -3, -1, 0], false);
+
+        assert_eq!((streaks.current, streaks.best), (2, 2));
+    }
+
+    #[test]
+    fn missed_day_is_frozen_with_freeze() {
+        let streaks: Streaks = streaks(&[-4, -3, -1, 0], true);
+
+        assert_eq!((streaks.current, streaks.best), (4, 4));
+    }
+
+    #[test]
+    fn second_missed_day_within_a_week_breaks_the_streak() {
+        let streaks: Streaks = streaks(&[-6, -4, -3, -1, 0], true);
+
+        assert_eq!((streaks.current, streaks.best), (2, 3));
+    }
+
+    #[test]
+    fn second_missed_day_a_week_later_is_frozen() {
+        let streaks: Streaks = streaks(&[-10, -8, -7, -6, -5, -4, -3, -1, 0], true);
+
+        assert_eq!((streaks.current, streaks.best), (9, 9));
+    }
+
+    #[test]
+    fn streak_ending_yesterday_is_still_running() {
+        let streaks: Streaks = streaks(&[-3, -2, -1], false);
+
+        assert_eq!((streaks.current, streaks.best), (3, 3));
+        assert_eq!(streaks.answered_today, 0);
+    }
+
+    #[test]
+    fn streak_ending_before_yesterday_is_over() {
+        let streaks: Streaks = streaks(&[-4, -3, -2], false);
+
+        assert_eq!((streaks.current, streaks.best), (0, 3));
+    }
+
+    #[test]
+    fn yesterday_missed_is_frozen_while_today_is_not_over() {
+        let streaks: Streaks = streaks(&[-4, -3, -2], true);
+
+        assert_eq!((streaks.current, streaks.best), (3, 3));
+    }
+}
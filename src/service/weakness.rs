@@ -0,0 +1,167 @@
+use crate::domain::{AnswerCount, GenderMistake, Language, Noun, NounAccuracy, UiLanguage};
+use crate::i18n;
+use crate::service::gender_rules;
+use std::cmp::{Ordering, Reverse};
+
+/// Answers a noun needs before it can be rated among the weakest.
+pub const MIN_NOUN_ANSWERS: u32 = 3;
+/// Answers an ending needs, spread over at least `MIN_ENDING_NOUNS` nouns,
+/// before it can be rated among the weakest.
+pub const MIN_ENDING_ANSWERS: u32 = 5;
+const MIN_ENDING_NOUNS: u32 = 2;
+/// Longest ending looked at, in letters.
+const MAX_ENDING_LENGTH: usize = 3;
+/// Endings and nouns listed in the report.
+pub const REPORT_SIZE: usize = 5;
+
+/// Answers given on the nouns sharing an ending, such as "ma".
+#[derive(Debug, Clone)]
+pub struct EndingAccuracy {
+    pub ending: String,
+    pub count: AnswerCount,
+    nouns: u32,
+}
+
+/// Endings of one to three letters answered least accurately, leaving out
+/// the ones without enough answers or without any mistake.
+pub fn weak_endings(accuracies: &[NounAccuracy]) -> Vec<EndingAccuracy> {
+    let mut endings: Vec<EndingAccuracy> = Vec::new();
+    for accuracy in accuracies {
+        let letters: Vec<char> = gender_rules::head_word(&accuracy.word).chars().collect();
+        let longest: usize = MAX_ENDING_LENGTH.min(letters.len().saturating_sub(1));
+        for length in 1..=longest {
+            let ending: String = letters[letters.len() - length..].iter().collect();
+            match endings
+                .iter_mut()
+                .find(|existing| existing.ending == ending)
+            {
+                Some(existing) => {
+                    existing.count.answered += accuracy.count.answered;
+                    existing.count.correct += accuracy.count.correct;
+                    existing.nouns += 1;
+                }
+                None => endings.push(EndingAccuracy {
+                    ending,
+                    count: accuracy.count.clone(),
+                    nouns: 1,
+                }),
+            }
+        }
+    }
+    endings.retain(|ending| {
+        ending.nouns >= MIN_ENDING_NOUNS
+            && ending.count.answered >= MIN_ENDING_ANSWERS
+            && ending.count.correct < ending.count.answered
+    });
+    endings.sort_by(|a, b| by_weakness(&a.count, &b.count));
+    endings.truncate(REPORT_SIZE);
+    endings
+}
+
+/// Nouns answered least accurately, leaving out the ones without enough
+/// answers or without any mistake.
+pub fn weak_nouns(accuracies: &[NounAccuracy]) -> Vec<&NounAccuracy> {
+    let mut nouns: Vec<&NounAccuracy> = accuracies
+        .iter()
+        .filter(|accuracy| {
+            accuracy.count.answered >= MIN_NOUN_ANSWERS
+                && accuracy.count.correct < accuracy.count.answered
+        })
+        .collect();
+    nouns.sort_by(|a, b| by_weakness(&a.count, &b.count));
+    nouns.truncate(REPORT_SIZE);
+    nouns
+}
+
+/// Lowest accuracy first, the most answered first among equals.
+fn by_weakness(a: &AnswerCount, b: &AnswerCount) -> Ordering {
+    a.accuracy()
+        .total_cmp(&b.accuracy())
+        .then(b.answered.cmp(&a.answered))
+}
+
+/// Report of the genders picked for each gender of the language, followed by
+/// the weakest endings and nouns.
+pub fn report(
+    ui_language: UiLanguage,
+    language: Language,
+    confusion: &[GenderMistake],
+    endings: &[EndingAccuracy],
+    nouns: &[(Noun, AnswerCount)],
+) -> String {
+    let learning: String = i18n::learning_language(ui_language, language);
+    let mut sections: Vec<String> = vec![i18n::format(
+        ui_language,
+        "weak-title",
+        &[("language", &learning)],
+    )];
+
+    let gender_rows: Vec<String> = language
+        .answer_genders()
+        .iter()
+        .filter_map(|gender| {
+            let mut cells: Vec<&GenderMistake> = confusion
+                .iter()
+                .filter(|cell| cell.gender == *gender)
+                .collect();
+            // Right answers first, then the wrong genders from the most picked.
+            cells.sort_by_key(|cell| (cell.chosen != *gender, Reverse(cell.times)));
+            let picks: Vec<String> = cells
+                .iter()
+                .map(|cell| {
+                    if cell.chosen == *gender {
+                        format!("{} ✓", cell.times)
+                    } else {
+                        let chosen: String = i18n::gender(ui_language, cell.chosen);
+                        format!("{} → {}", cell.times, chosen)
+                    }
+                })
+                .collect();
+            (!picks.is_empty()).then(|| {
+                let gender: String = i18n::gender(ui_language, *gender);
+                format!("  {}: {}", gender, picks.join(", "))
+            })
+        })
+        .collect();
+    if !gender_rows.is_empty() {
+        let title: String = i18n::text(ui_language, "weak-genders");
+        sections.push(format!("{}\n{}", title, gender_rows.join("\n")));
+    }
+
+    if !endings.is_empty() {
+        let rows: Vec<String> = endings
+            .iter()
+            .map(|ending| format!("  -{}: {}", ending.ending, format_count(&ending.count)))
+            .collect();
+        let title: String = i18n::text(ui_language, "weak-endings");
+        sections.push(format!("{}\n{}", title, rows.join("\n")));
+    }
+    if !nouns.is_empty() {
+        let rows: Vec<String> = nouns
+            .iter()
+            .map(|(noun, count)| format!("  {}: {}", noun.with_article(), format_count(count)))
+            .collect();
+        let title: String = i18n::text(ui_language, "weak-nouns");
+        sections.push(format!("{}\n{}", title, rows.join("\n")));
+    }
+    if endings.is_empty() && nouns.is_empty() {
+        sections.push(i18n::format(
+            ui_language,
+            "weak-no-patterns",
+            &[
+                ("ending_answers", &MIN_ENDING_ANSWERS),
+                ("noun_answers", &MIN_NOUN_ANSWERS),
+            ],
+        ));
+    }
+    sections.join("\n\n")
+}
+
+fn format_count(count: &AnswerCount) -> String {
+    format!(
+        "{:.0}% ({}/{})",
+        count.accuracy(),
+        count.correct,
+        count.answered
+    )
+}
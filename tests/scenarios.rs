@@ -0,0 +1,1287 @@
+use el_la_la::client::chat::{ChatClient, ChatEvent, Choice, IncomingChoice, IncomingMessage};
+use el_la_la::domain::{
+    Answer, Difficulty, ExerciseType, Gender, Language, Noun, PlayOutcome, QuizMode, UiLanguage,
+};
+use el_la_la::i18n;
+use el_la_la::repository::in_memory::InMemoryDatabase;
+use el_la_la::service::bot::BotService;
+use std::cell::RefCell;
+use std::error::Error;
+use std::rc::Rc;
+use std::sync::mpsc::channel;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const CHAT_ID: i64 = 42;
+const USER_ID: u64 = 4242;
+
+#[derive(Debug, Clone, PartialEq)]
+enum Sent {
+    Message(String),
+    Reply(i32, String),
+    Question(String, Vec<(String, String)>),
+    Menu(String, Vec<Vec<(String, String)>>),
+    Edit(i32, String),
+    MenuEdit(i32, String, Vec<Vec<(String, String)>>),
+    Reaction(i32, String),
+    Acknowledgement(Option<String>),
+}
+
+#[derive(Clone, Default)]
+struct RecordingChatClient {
+    sent: Rc<RefCell<Vec<Sent>>>,
+}
+
+impl ChatClient for RecordingChatClient {
+    fn send_message(&self, _chat_id: i64, text: &str) -> Result<(), Box<dyn Error>> {
+        self.sent.borrow_mut().push(Sent::Message(text.to_string()));
+        Ok(())
+    }
+
+    fn send_reply(
+        &self,
+        _chat_id: i64,
+        reply_to_message_id: i32,
+        text: &str,
+    ) -> Result<(), Box<dyn Error>> {
+        let sent = Sent::Reply(reply_to_message_id, text.to_string());
+        self.sent.borrow_mut().push(sent);
+        Ok(())
+    }
+
+    fn send_question(
+        &self,
+        _chat_id: i64,
+        text: &str,
+        choices: &[Choice],
+    ) -> Result<(), Box<dyn Error>> {
+        let sent = Sent::Question(text.to_string(), recorded_choices(choices));
+        self.sent.borrow_mut().push(sent);
+        Ok(())
+    }
+
+    fn send_menu(
+        &self,
+        _chat_id: i64,
+        text: &str,
+        rows: &[Vec<Choice>],
+    ) -> Result<(), Box<dyn Error>> {
+        let sent = Sent::Menu(text.to_string(), recorded_rows(rows));
+        self.sent.borrow_mut().push(sent);
+        Ok(())
+    }
+
+    fn edit_message(
+        &self,
+        _chat_id: i64,
+        message_id: i32,
+        text: &str,
+    ) -> Result<(), Box<dyn Error>> {
+        let sent = Sent::Edit(message_id, text.to_string());
+        self.sent.borrow_mut().push(sent);
+        Ok(())
+    }
+
+    fn edit_menu(
+        &self,
+        _chat_id: i64,
+        message_id: i32,
+        text: &str,
+        rows: &[Vec<Choice>],
+    ) -> Result<(), Box<dyn Error>> {
+        let sent = Sent::MenuEdit(message_id, text.to_string(), recorded_rows(rows));
+        self.sent.borrow_mut().push(sent);
+        Ok(())
+    }
+
+    fn react(&self, _chat_id: i64, message_id: i32, reaction: &str) -> Result<(), Box<dyn Error>> {
+        let sent = Sent::Reaction(message_id, reaction.to_string());
+        self.sent.borrow_mut().push(sent);
+        Ok(())
+    }
+
+    fn acknowledge_choice(
+        &self,
+        _choice_id: &str,
+        text: Option<&str>,
+    ) -> Result<(), Box<dyn Error>> {
+        let sent = Sent::Acknowledgement(text.map(str::to_string));
+        self.sent.borrow_mut().push(sent);
+        Ok(())
+    }
+}
+
+fn recorded_choices(choices: &[Choice]) -> Vec<(String, String)> {
+    choices
+        .iter()
+        .map(|choice| (choice.label.clone(), choice.data.clone()))
+        .collect()
+}
+
+fn recorded_rows(rows: &[Vec<Choice>]) -> Vec<Vec<(String, String)>> {
+    rows.iter().map(|row| recorded_choices(row)).collect()
+}
+
+fn noun(id: i32, english: &str, spanish: &str, gender: Gender) -> Noun {
+    Noun {
+        id,
+        english: english.to_string(),
+        word: spanish.to_string(),
+        language: Language::Spanish,
+        gender,
+        frequency_rank: id as u32,
+        plural: None,
+        stressed_a: false,
+    }
+}
+
+fn database() -> InMemoryDatabase {
+    InMemoryDatabase::with_nouns(vec![
+        noun(1, "house", "casa", Gender::Feminine),
+        noun(2, "time", "tiempo", Gender::Masculine),
+        noun(3, "idiot", "idiota", Gender::Any),
+    ])
+}
+
+fn message(message_id: i32, text: &str) -> ChatEvent {
+    ChatEvent::Message(IncomingMessage {
+        chat_id: CHAT_ID,
+        user_id: USER_ID,
+        language_code: None,
+        message_id,
+        text: text.to_string(),
+    })
+}
+
+fn choice(message_id: i32, data: &str) -> ChatEvent {
+    ChatEvent::Choice(IncomingChoice {
+        id: format!("choice-{}", message_id),
+        chat_id: CHAT_ID,
+        user_id: USER_ID,
+        language_code: None,
+        message_id,
+        question_text: Some("Question".to_string()),
+        data: data.to_string(),
+    })
+}
+
+/// Tick at `minutes` after midnight UTC on the given day since the epoch.
+fn tick(day: u64, minutes: u64) -> ChatEvent {
+    ChatEvent::Tick(UNIX_EPOCH + Duration::from_secs(day * 24 * 60 * 60 + minutes * 60))
+}
+
+fn is_reminder(sent: &Sent) -> bool {
+    matches!(sent, Sent::Menu(text, _) if text.starts_with("⏰ Time to practise!"))
+}
+
+/// Feeds the events to a bot backed by `database` and returns what it sent.
+fn play(database: &InMemoryDatabase, events: Vec<ChatEvent>) -> Vec<Sent> {
+    play_as_admin(database, Vec::new(), events)
+}
+
+/// Same as [`play`], with the given Telegram users let run admin commands.
+fn play_as_admin(
+    database: &InMemoryDatabase,
+    admin_user_ids: Vec<u64>,
+    events: Vec<ChatEvent>,
+) -> Vec<Sent> {
+    let chat_client = RecordingChatClient::default();
+    let (event_sender, event_receiver) = channel();
+    for event in events {
+        event_sender.send(event).unwrap();
+    }
+    drop(event_sender);
+
+    BotService::new(chat_client.clone(), database.repositories())
+        .with_admins(admin_user_ids)
+        .run(event_receiver)
+        .unwrap();
+    let sent: Vec<Sent> = chat_client.sent.borrow().clone();
+    sent
+}
+
+fn question(play_id: i32, spanish: &str, english: &str) -> Sent {
+    let choices: Vec<(String, String)> = [
+        ("Masculine", "masculine"),
+        ("Feminine", "feminine"),
+        ("Any", "any"),
+    ]
+    .iter()
+    .map(|(label, answer)| (label.to_string(), format!("answer:{}:{}", play_id, answer)))
+    .collect();
+    Sent::Menu(
+        format!("What's the gender of '{}' ({})?", spanish, english),
+        vec![choices, action_choices(play_id)],
+    )
+}
+
+fn action_choices(play_id: i32) -> Vec<(String, String)> {
+    vec![
+        ("⏭ Skip".to_string(), format!("answer:{}:skip", play_id)),
+        (
+            "🤷 I don't know".to_string(),
+            format!("answer:{}:unknown", play_id),
+        ),
+    ]
+}
+
+#[test]
+fn start_registers_the_user_and_asks_a_question() {
+    let database = database();
+
+    let sent = play(&database, vec![message(1, "/start")]);
+
+    assert!(
+        matches!(&sent[0], Sent::Message(text) if text.starts_with("Welcome to 'El la la' game."))
+    );
+    assert_eq!(sent[1], question(1, "casa", "house"));
+    assert_eq!(database.users().len(), 1);
+    assert_eq!(database.users()[0].telegram_user_id, USER_ID);
+    assert_eq!(database.plays().len(), 1);
+    assert_eq!(database.plays()[0].answer, None);
+}
+
+#[test]
+fn correct_choice_is_graded_and_the_next_question_follows() {
+    let database = database();
+    play(&database, vec![message(1, "/start")]);
+
+    let sent = play(&database, vec![choice(2, "answer:1:feminine")]);
+
+    assert_eq!(
+        sent,
+        vec![
+            Sent::Acknowledgement(None),
+            Sent::Edit(2, "Question\n\n✅ Feminine".to_string()),
+            question(2, "tiempo", "time"),
+        ]
+    );
+    assert_eq!(database.plays()[0].answer, Some(true));
+    assert_eq!(database.reviews()[0].repetitions, 1);
+}
+
+#[test]
+fn wrong_choice_reveals_the_correct_article() {
+    let database = database();
+    play(&database, vec![message(1, "/start")]);
+
+    let sent = play(&database, vec![choice(2, "answer:1:masculine")]);
+
+    assert_eq!(
+        sent[1],
+        Sent::Edit(2, "Question\n\n❌ Masculine".to_string())
+    );
+    let expected = "la casa — feminine\n\
+        'la casa' follows the rule: nouns ending in -a are feminine.";
+    assert_eq!(sent[2], Sent::Reply(2, expected.to_string()));
+    assert_eq!(database.plays()[0].answer, Some(false));
+    assert_eq!(database.reviews()[0].repetitions, 0);
+}
+
+#[test]
+fn wrong_answer_on_any_gender_noun_explains_both_articles() {
+    let database = database();
+    play(
+        &database,
+        vec![
+            message(1, "/start"),
+            choice(2, "answer:1:feminine"),
+            choice(3, "answer:2:masculine"),
+        ],
+    );
+
+    let sent = play(&database, vec![choice(4, "answer:3:masculine")]);
+
+    let expected = "el/la idiota — any: both 'el idiota' and 'la idiota' are valid\n\
+        'el/la idiota' takes either article, although nouns ending in -a are feminine.";
+    assert_eq!(sent[2], Sent::Reply(4, expected.to_string()));
+}
+
+#[test]
+fn reveal_toggles_showing_the_correct_answer() {
+    let database = database();
+    play(&database, vec![message(1, "/start")]);
+
+    let sent = play(
+        &database,
+        vec![message(2, "/reveal"), choice(3, "answer:1:masculine")],
+    );
+
+    assert_eq!(
+        sent[0],
+        Sent::Message("The correct answer will no longer be shown after a mistake.".to_string())
+    );
+    assert!(!sent.iter().any(|sent| matches!(sent, Sent::Reply(..))));
+    assert!(!database.users()[0].settings.show_correct_answer);
+}
+
+#[test]
+fn typed_answer_is_graded_with_a_reaction() {
+    let database = database();
+    play(&database, vec![message(1, "/start")]);
+
+    let sent = play(&database, vec![message(2, "Feminine")]);
+
+    assert!(matches!(&sent[0], Sent::Reaction(2, reaction) if reaction != "💩"));
+    assert_eq!(sent[1], question(2, "tiempo", "time"));
+    assert_eq!(database.plays()[0].answer, Some(true));
+}
+
+#[test]
+fn answers_are_recorded_as_chosen_and_typed() {
+    let database = database();
+    play(&database, vec![message(1, "/start")]);
+
+    play(
+        &database,
+        vec![message(2, " La "), choice(3, "answer:2:feminine")],
+    );
+
+    let plays = database.plays();
+    assert_eq!(
+        plays[0].chosen_answer,
+        Some(Answer::Gender(Gender::Feminine))
+    );
+    assert_eq!(plays[0].answer_text, Some("La".to_string()));
+    assert!(plays[0].latency_ms.is_some());
+    assert_eq!(plays[1].exercise_type, ExerciseType::Gender);
+    assert_eq!(
+        plays[1].chosen_answer,
+        Some(Answer::Gender(Gender::Feminine))
+    );
+    assert_eq!(plays[1].answer_text, None);
+    assert_eq!(plays[1].answer, Some(false));
+}
+
+#[test]
+fn answering_an_old_question_is_rejected() {
+    let database = database();
+    play(
+        &database,
+        vec![message(1, "/start"), choice(2, "answer:1:feminine")],
+    );
+
+    let sent = play(&database, vec![choice(2, "answer:1:masculine")]);
+
+    assert_eq!(
+        sent,
+        vec![Sent::Acknowledgement(Some(
+            "This question has expired.".to_string()
+        ))]
+    );
+    assert_eq!(database.plays()[0].answer, Some(true));
+}
+
+#[test]
+fn stop_abandons_the_pending_question() {
+    let database = database();
+    play(&database, vec![message(1, "/start")]);
+
+    let sent = play(&database, vec![message(2, "/stop")]);
+
+    assert_eq!(
+        sent,
+        vec![Sent::Message(
+            "Stopping the game for now.\nSend /help for further information".to_string()
+        )]
+    );
+    let plays = database.plays();
+    assert_eq!(plays.len(), 1);
+    assert_eq!(plays[0].outcome, Some(PlayOutcome::Abandoned));
+    assert_eq!(plays[0].answer, None);
+}
+
+#[test]
+fn help_lists_the_commands() {
+    let database = database();
+
+    let sent = play(&database, vec![message(1, "/help")]);
+
+    let [Sent::Message(text)] = sent.as_slice() else {
+        panic!("Unexpected messages: {:?}", sent);
+    };
+    for command in ["/start", "/stop", "/stats", "/reveal"] {
+        assert!(text.contains(command), "{} missing from help", command);
+    }
+}
+
+#[test]
+fn stats_summarise_the_answers() {
+    let database = database();
+    play(
+        &database,
+        vec![
+            message(1, "/start"),
+            choice(2, "answer:1:feminine"),
+            choice(3, "answer:2:feminine"),
+        ],
+    );
+
+    let sent = play(&database, vec![message(4, "/stats")]);
+
+    let [Sent::Message(text)] = sent.as_slice() else {
+        panic!("Unexpected messages: {:?}", sent);
+    };
+    assert!(text.contains("Answered: 2"));
+    assert!(text.contains("Accuracy: 50% (1/2)"));
+    assert!(text.contains("Masculine: 0% (0/1), mostly mistaken for Feminine"));
+    assert!(text.contains("Feminine: 100% (1/1)"));
+    assert!(text.contains("Answered today: 2"));
+    assert!(text.contains("Current streak: 0"));
+}
+
+#[test]
+fn stats_without_answers_invite_to_play() {
+    let database = database();
+
+    let sent = play(&database, vec![message(1, "/stats")]);
+
+    assert_eq!(
+        sent,
+        vec![Sent::Message(
+            "You haven't answered any question yet.\nSend /start to play.".to_string()
+        )]
+    );
+}
+
+#[test]
+fn answer_without_a_question_fails_without_stopping_the_bot() {
+    let database = database();
+
+    let sent = play(&database, vec![message(1, "feminine"), message(2, "/help")]);
+
+    assert_eq!(sent.len(), 1);
+    assert!(matches!(&sent[0], Sent::Message(text) if text.contains("/start")));
+    assert!(database.plays().is_empty());
+}
+
+#[test]
+fn unexpected_choice_data_is_ignored() {
+    let database = database();
+    play(&database, vec![message(1, "/start")]);
+
+    let sent = play(&database, vec![choice(2, "something-else")]);
+
+    assert!(sent.is_empty());
+    assert_eq!(database.plays()[0].answer, None);
+}
+
+#[test]
+fn missing_nouns_fail_the_question_only() {
+    let database = InMemoryDatabase::default();
+
+    let sent = play(&database, vec![message(1, "/start")]);
+
+    assert_eq!(sent.len(), 1);
+    assert!(database.plays().is_empty());
+}
+
+#[test]
+fn typed_articles_and_synonyms_are_understood() {
+    for (text, is_correct) in [
+        ("la", true),
+        ("  LA. ", true),
+        ("f", true),
+        ("Femenína", true),
+        ("él", false),
+        ("masculino", false),
+        ("el/la", false),
+        ("ambos", false),
+    ] {
+        let database = database();
+        play(&database, vec![message(1, "/start")]);
+
+        let sent = play(&database, vec![message(2, text)]);
+
+        let is_positive = matches!(&sent[0], Sent::Reaction(2, reaction) if reaction != "💩");
+        assert_eq!(is_positive, is_correct, "unexpected reaction to '{}'", text);
+        assert_eq!(database.plays()[0].answer, Some(is_correct));
+    }
+}
+
+#[test]
+fn unrecognised_answer_is_not_graded() {
+    let database = database();
+    play(&database, vec![message(1, "/start")]);
+
+    let sent = play(&database, vec![message(2, "no idea")]);
+
+    let expected = "Sorry, I didn't understand 'no idea'.\n\
+        Answer with el, la or el/la (or masculine, feminine, any).";
+    assert_eq!(sent, vec![Sent::Reply(2, expected.to_string())]);
+    assert_eq!(database.plays()[0].answer, None);
+    assert!(database.reviews().is_empty());
+}
+
+fn article_mode(database: &InMemoryDatabase) {
+    play(
+        database,
+        vec![message(1, "/mode"), choice(2, "mode:article")],
+    );
+}
+
+#[test]
+fn mode_switches_to_article_questions() {
+    let database =
+        InMemoryDatabase::with_nouns(vec![noun(1, "weekend", "fin de semana", Gender::Masculine)]);
+
+    let sent = play(
+        &database,
+        vec![message(1, "/mode"), choice(2, "mode:article")],
+    );
+
+    assert!(matches!(&sent[0], Sent::Question(_, choices) if choices.len() == 3));
+    assert_eq!(
+        sent[2],
+        Sent::Edit(
+            2,
+            "Quiz mode: Articles.\nIt applies from the next question.".to_string()
+        )
+    );
+    assert_eq!(database.users()[0].settings.quiz_mode, QuizMode::Article);
+
+    let sent = play(&database, vec![message(3, "/start")]);
+
+    let Sent::Menu(text, rows) = &sent[1] else {
+        panic!("Unexpected messages: {:?}", sent);
+    };
+    assert_eq!(text, "Fill in the article: ___ fin de semana (weekend)");
+    assert_eq!(rows[1], action_choices(1));
+    let choices = &rows[0];
+    let articles: Vec<&str> = choices.iter().map(|(label, _)| label.as_str()).collect();
+    assert!(articles == ["el", "la", "los", "las"] || articles == ["un", "una", "unos", "unas"]);
+    assert_eq!(
+        database.plays()[0].exercise_type,
+        ExerciseType::SingularArticle
+    );
+
+    let masculine_choice: String = choices[0].1.clone();
+    let sent = play(&database, vec![choice(4, &masculine_choice)]);
+
+    let expected = format!("Question\n\n✅ {}", articles[0]);
+    assert_eq!(sent[1], Sent::Edit(4, expected));
+    assert_eq!(database.plays()[0].answer, Some(true));
+}
+
+fn german_noun(id: i32, english: &str, german: &str, gender: Gender) -> Noun {
+    Noun {
+        language: Language::German,
+        ..noun(id, english, german, gender)
+    }
+}
+
+#[test]
+fn german_nouns_are_asked_with_a_neuter_choice() {
+    let database = InMemoryDatabase::with_nouns(vec![
+        noun(1, "house", "casa", Gender::Feminine),
+        german_noun(2, "house", "Haus", Gender::Neuter),
+        german_noun(3, "chair", "Stuhl", Gender::Masculine),
+    ]);
+    article_mode(&database);
+
+    let sent = play(
+        &database,
+        vec![message(3, "/language"), choice(4, "language:de")],
+    );
+
+    assert!(matches!(&sent[0], Sent::Question(_, choices) if choices.len() == 5));
+    let expected = "🇩🇪 Now learning German.\n\
+        It applies from the next question.\n\
+        Article questions are only asked in Spanish, so you'll get gender questions.";
+    assert_eq!(sent[2], Sent::Edit(4, expected.to_string()));
+    assert_eq!(database.users()[0].settings.language, Language::German);
+
+    let sent = play(&database, vec![message(5, "/start")]);
+
+    let choices: Vec<(String, String)> = [
+        ("Masculine", "masculine"),
+        ("Feminine", "feminine"),
+        ("Neuter", "neuter"),
+    ]
+    .iter()
+    .map(|(label, answer)| (label.to_string(), format!("answer:1:{}", answer)))
+    .collect();
+    assert_eq!(
+        sent[1],
+        Sent::Menu(
+            "What's the gender of 'Haus' (house)?".to_string(),
+            vec![choices, action_choices(1)]
+        )
+    );
+
+    let sent = play(&database, vec![message(6, "das"), message(7, "die")]);
+
+    assert!(matches!(&sent[0], Sent::Reaction(6, reaction) if reaction != "💩"));
+    assert_eq!(sent[3], Sent::Reply(7, "der Stuhl — masculine".to_string()));
+    assert_eq!(database.plays()[0].answer, Some(true));
+    assert_eq!(database.plays()[1].answer, Some(false));
+
+    let sent = play(&database, vec![message(8, "any"), message(9, "/why")]);
+
+    let expected = "Sorry, I didn't understand 'any'.\n\
+        Answer with der, die or das (or masculine, feminine, neuter).";
+    assert_eq!(sent[0], Sent::Reply(8, expected.to_string()));
+    let expected = "der Stuhl — masculine\nNo ending rules are available for German yet.";
+    assert_eq!(sent[1], Sent::Message(expected.to_string()));
+}
+
+#[test]
+fn welcome_and_help_name_the_learned_language_and_its_articles() {
+    let database =
+        InMemoryDatabase::with_nouns(vec![german_noun(1, "house", "Haus", Gender::Neuter)]);
+    play(
+        &database,
+        vec![message(1, "/language"), choice(2, "language:de")],
+    );
+
+    let sent = play(&database, vec![message(3, "/help"), message(4, "/start")]);
+
+    assert!(
+        matches!(&sent[0], Sent::Message(text) if text.ends_with("such as der, die or das.\n"))
+    );
+    assert!(matches!(&sent[1], Sent::Message(text) if text.contains("on German nouns' gender")));
+}
+
+#[test]
+fn stressed_a_nouns_take_el_in_the_singular() {
+    let agua = Noun {
+        stressed_a: true,
+        ..noun(1, "water", "agua", Gender::Feminine)
+    };
+    let database = InMemoryDatabase::with_nouns(vec![agua]);
+    article_mode(&database);
+    play(&database, vec![message(3, "/start")]);
+
+    let sent = play(&database, vec![message(4, "La")]);
+
+    let singular = "el agua — feminine, but the stressed 'a' takes 'el' in the singular\n";
+    let plural = "las aguas — feminine\n";
+    assert!(matches!(&sent[0], Sent::Reaction(4, reaction) if reaction == "💩"));
+    assert!(
+        matches!(&sent[1], Sent::Reply(4, text)
+            if text.starts_with(singular) || text.starts_with(plural)),
+        "Unexpected reply: {:?}",
+        sent[1]
+    );
+}
+
+#[test]
+fn unrecognised_article_is_not_graded() {
+    let database = database();
+    article_mode(&database);
+    play(&database, vec![message(3, "/start")]);
+
+    let sent = play(&database, vec![message(4, "feminine")]);
+
+    let expected = "Sorry, I didn't understand 'feminine'.\n\
+        Answer with an article: el, la, los, las, un, una, unos or unas.";
+    assert_eq!(sent, vec![Sent::Reply(4, expected.to_string())]);
+    assert_eq!(database.plays()[0].answer, None);
+}
+
+#[test]
+fn gender_question_explains_the_stressed_a_rule() {
+    let agua = Noun {
+        stressed_a: true,
+        ..noun(1, "water", "agua", Gender::Feminine)
+    };
+    let database = InMemoryDatabase::with_nouns(vec![agua]);
+    play(&database, vec![message(1, "/start")]);
+
+    let sent = play(&database, vec![choice(2, "answer:1:masculine")]);
+
+    let expected = "el agua — feminine, but the stressed 'a' takes 'el' in the singular\n\
+        'el agua' follows the rule: nouns ending in -a are feminine.";
+    assert_eq!(sent[2], Sent::Reply(2, expected.to_string()));
+}
+
+#[test]
+fn why_explains_greek_nouns_ending_in_ma() {
+    let database =
+        InMemoryDatabase::with_nouns(vec![noun(1, "problem", "problema", Gender::Masculine)]);
+    play(&database, vec![message(1, "/start")]);
+
+    let sent = play(
+        &database,
+        vec![choice(2, "answer:1:feminine"), message(3, "/why")],
+    );
+
+    let expected = "el problema — masculine\n\
+        'el problema' follows the rule: nouns of Greek origin ending in -ma are masculine.";
+    assert_eq!(sent.last(), Some(&Sent::Message(expected.to_string())));
+}
+
+#[test]
+fn why_points_out_exceptions() {
+    let database = InMemoryDatabase::with_nouns(vec![noun(1, "hand", "mano", Gender::Feminine)]);
+    play(
+        &database,
+        vec![message(1, "/start"), choice(2, "answer:1:feminine")],
+    );
+
+    let sent = play(&database, vec![message(3, "/why")]);
+
+    let expected = "la mano — feminine\n\
+        'la mano' is an exception to the rule: nouns ending in -o are masculine.";
+    assert_eq!(sent, vec![Sent::Message(expected.to_string())]);
+}
+
+#[test]
+fn why_needs_an_answered_question() {
+    let database = database();
+    play(&database, vec![message(1, "/start")]);
+
+    let sent = play(&database, vec![message(2, "/why")]);
+
+    let expected = "There is no answered question to explain yet.";
+    assert_eq!(sent, vec![Sent::Message(expected.to_string())]);
+}
+
+fn menu_labels(sent: &Sent) -> Vec<String> {
+    let (Sent::Menu(_, rows) | Sent::MenuEdit(_, _, rows)) = sent else {
+        panic!("Not a menu: {:?}", sent);
+    };
+    rows.concat().into_iter().map(|(label, _)| label).collect()
+}
+
+#[test]
+fn settings_menu_edits_itself_as_options_change() {
+    let database = database();
+
+    let sent = play(&database, vec![message(1, "/settings")]);
+
+    let labels: Vec<String> = menu_labels(&sent[0]);
+    assert!(labels.contains(&"Daily goal: 20 answers".to_string()));
+    assert!(labels.contains(&"Timezone: UTC+00:00".to_string()));
+
+    let sent = play(
+        &database,
+        vec![
+            choice(2, "settings:daily_goal"),
+            choice(2, "settings:timezone_later"),
+            choice(2, "settings:timezone_later"),
+            choice(2, "settings:reminder"),
+            choice(2, "settings:difficulty"),
+        ],
+    );
+
+    let labels: Vec<String> = menu_labels(&sent[9]);
+    assert!(labels.contains(&"Daily goal: 30 answers".to_string()));
+    assert!(labels.contains(&"Timezone: UTC+02:00".to_string()));
+    assert!(labels.contains(&"Reminder: 09:00".to_string()));
+    assert!(labels.contains(&"Difficulty: Intermediate (top 500 nouns)".to_string()));
+    let settings = database.users()[0].settings.clone();
+    assert_eq!(settings.daily_goal, 30);
+    assert_eq!(settings.utc_offset_minutes, 120);
+    assert_eq!(settings.reminder_time, Some(9 * 60));
+
+    let sent = play(&database, vec![choice(2, "settings:done")]);
+
+    assert!(matches!(&sent[1], Sent::Edit(2, text) if text.contains("Daily goal: 30 answers")));
+}
+
+#[test]
+fn new_nouns_are_picked_within_the_level() {
+    let rare = noun(1000, "rare", "rareza", Gender::Feminine);
+    let common = noun(5, "time", "tiempo", Gender::Masculine);
+    let database = InMemoryDatabase::with_nouns(vec![rare, common]);
+
+    let sent = play(&database, vec![message(1, "/start")]);
+
+    assert_eq!(sent[1], question(1, "tiempo", "time"));
+}
+
+#[test]
+fn accurate_learners_are_promoted_to_the_next_level() {
+    let nouns: Vec<Noun> = (1..=101)
+        .map(|id| noun(id, "house", &format!("casa{}", id), Gender::Feminine))
+        .collect();
+    let database = InMemoryDatabase::with_nouns(nouns);
+    let mut events: Vec<ChatEvent> = vec![message(1, "/start")];
+    for play_id in 1..=30 {
+        events.push(choice(play_id + 1, &format!("answer:{}:feminine", play_id)));
+    }
+
+    let sent = play(&database, events);
+
+    let level_up = "🎉 Level up! You are now Intermediate and will practise the top 500 nouns.";
+    assert!(sent.contains(&Sent::Message(level_up.to_string())));
+    assert_eq!(
+        database.users()[0].settings.difficulty,
+        Difficulty::Intermediate
+    );
+}
+
+#[test]
+fn mistakes_review_drills_wrong_nouns_until_right() {
+    let database = database();
+
+    let sent = play(
+        &database,
+        vec![
+            message(1, "/start"),
+            choice(2, "answer:1:masculine"),
+            choice(3, "answer:2:masculine"),
+            message(4, "/mistakes"),
+            choice(5, "answer:4:any"),
+            choice(6, "answer:5:feminine"),
+        ],
+    );
+
+    let review_start = Sent::Message(
+        "📝 Reviewing 1 noun you got wrong.\nSend /stop to finish early.".to_string(),
+    );
+    let start: usize = sent.iter().position(|sent| *sent == review_start).unwrap();
+    assert_eq!(sent[start + 1], question(4, "casa", "house"));
+    assert!(sent[start..].contains(&question(5, "casa", "house")));
+    let summary = "📝 Mistakes reviewed\n\n\
+        Answered: 2\n\
+        Correct: 1 (50%)\n\
+        Every noun was answered right in the end.\n\n\
+        Send /start to keep playing.";
+    assert_eq!(sent.last(), Some(&Sent::Message(summary.to_string())));
+    // The question pending when /mistakes was sent is kept as abandoned.
+    let outcomes: Vec<Option<PlayOutcome>> =
+        database.plays().iter().map(|play| play.outcome).collect();
+    assert_eq!(
+        outcomes,
+        vec![
+            Some(PlayOutcome::Incorrect),
+            Some(PlayOutcome::Correct),
+            Some(PlayOutcome::Abandoned),
+            Some(PlayOutcome::Incorrect),
+            Some(PlayOutcome::Correct),
+        ]
+    );
+}
+
+#[test]
+fn mistakes_review_carries_on_after_a_restart() {
+    let database = database();
+    play(
+        &database,
+        vec![
+            message(1, "/start"),
+            choice(2, "answer:1:masculine"),
+            choice(3, "answer:2:masculine"),
+            message(4, "/mistakes"),
+        ],
+    );
+
+    // A new bot over the same storage, as after a redeploy.
+    let sent = play(&database, vec![choice(5, "answer:4:feminine")]);
+
+    let summary = "📝 Mistakes reviewed\n\n\
+        Answered: 1\n\
+        Correct: 1 (100%)\n\
+        Every noun was answered right in the end.\n\n\
+        Send /start to keep playing.";
+    assert_eq!(sent.last(), Some(&Sent::Message(summary.to_string())));
+}
+
+#[test]
+fn mistakes_review_without_mistakes() {
+    let database = database();
+
+    let sent = play(&database, vec![message(1, "/mistakes")]);
+
+    assert_eq!(
+        sent,
+        vec![Sent::Message(
+            "You have no mistakes to review. 🎉\nSend /start to play.".to_string()
+        )]
+    );
+}
+
+#[test]
+fn stopping_a_mistakes_review_summarises_it() {
+    let database = database();
+
+    let sent = play(
+        &database,
+        vec![
+            message(1, "/start"),
+            choice(2, "answer:1:masculine"),
+            message(3, "/mistakes"),
+            choice(4, "answer:3:masculine"),
+            message(5, "/stop"),
+        ],
+    );
+
+    let summary = "📝 Mistakes reviewed\n\n\
+        Answered: 1\n\
+        Correct: 0 (0%)\n\
+        Still to practise: la casa";
+    let stop_position: usize = sent.len() - 3;
+    assert_eq!(sent[stop_position], Sent::Message(summary.to_string()));
+}
+
+#[test]
+fn reminder_is_sent_once_a_day_after_its_time() {
+    let database = database();
+    play(
+        &database,
+        vec![message(1, "/settings"), choice(2, "settings:reminder")],
+    );
+
+    assert_eq!(play(&database, vec![tick(20_000, 8 * 60 + 59)]), vec![]);
+    let sent = play(
+        &database,
+        vec![tick(20_000, 9 * 60), tick(20_000, 9 * 60 + 1)],
+    );
+    let expected = Sent::Menu(
+        "⏰ Time to practise!\n\
+        You haven't answered any question today. Your daily goal is 20 answers."
+            .to_string(),
+        vec![
+            vec![("▶️ Play now".to_string(), "reminder:play".to_string())],
+            vec![("🔕 Stop reminders".to_string(), "reminder:off".to_string())],
+        ],
+    );
+    assert_eq!(sent, vec![expected]);
+    assert_eq!(play(&database, vec![tick(20_000, 20 * 60)]), vec![]);
+    let sent = play(&database, vec![tick(20_001, 10 * 60)]);
+    assert!(sent.iter().all(is_reminder) && sent.len() == 1);
+}
+
+#[test]
+fn reminder_time_is_local_to_the_user() {
+    let database = database();
+    play(
+        &database,
+        vec![
+            message(1, "/settings"),
+            choice(2, "settings:reminder"),
+            choice(2, "settings:timezone_later"),
+            choice(2, "settings:timezone_later"),
+        ],
+    );
+
+    assert_eq!(play(&database, vec![tick(20_000, 6 * 60 + 59)]), vec![]);
+    let sent = play(&database, vec![tick(20_000, 7 * 60)]);
+    assert!(sent.iter().all(is_reminder) && sent.len() == 1);
+}
+
+#[test]
+fn users_who_practised_today_are_not_reminded() {
+    let database = database();
+    play(
+        &database,
+        vec![
+            message(1, "/start"),
+            choice(2, "answer:1:feminine"),
+            message(3, "/settings"),
+            choice(4, "settings:reminder"),
+        ],
+    );
+
+    let now: u64 = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    let sent = play(&database, vec![tick(now / (24 * 60 * 60), 23 * 60 + 59)]);
+
+    assert_eq!(sent, vec![]);
+}
+
+#[test]
+fn play_now_on_a_reminder_asks_a_question() {
+    let database = database();
+    play(
+        &database,
+        vec![message(1, "/settings"), choice(2, "settings:reminder")],
+    );
+    play(&database, vec![tick(20_000, 9 * 60)]);
+
+    let sent = play(&database, vec![choice(3, "reminder:play")]);
+
+    assert_eq!(sent[0], Sent::Acknowledgement(None));
+    assert_eq!(sent[1], Sent::Edit(3, "Question".to_string()));
+    assert_eq!(sent[2], question(1, "casa", "house"));
+}
+
+#[test]
+fn reminders_can_be_turned_off_from_a_reminder() {
+    let database = database();
+    play(
+        &database,
+        vec![message(1, "/settings"), choice(2, "settings:reminder")],
+    );
+    play(&database, vec![tick(20_000, 9 * 60)]);
+
+    let sent = play(&database, vec![choice(3, "reminder:off")]);
+
+    let expected = "🔕 Reminders are off. Turn them back on in /settings.";
+    assert_eq!(sent[1], Sent::Edit(3, expected.to_string()));
+    assert_eq!(database.users()[0].settings.reminder_time, None);
+    assert_eq!(play(&database, vec![tick(20_001, 9 * 60)]), vec![]);
+}
+
+#[test]
+fn reaching_the_daily_goal_reports_the_streak() {
+    let database = database();
+    let mut events: Vec<ChatEvent> = vec![message(1, "/settings")];
+    events.extend((0..4).map(|_| choice(2, "settings:daily_goal")));
+    events.push(message(3, "/start"));
+    for play_id in 1..=10 {
+        events.push(choice(
+            play_id + 3,
+            &format!("answer:{}:masculine", play_id),
+        ));
+    }
+
+    let sent = play(&database, events);
+
+    let goal_reached = Sent::Message("🎯 10/10 — 1-day streak!".to_string());
+    assert_eq!(sent.iter().filter(|sent| **sent == goal_reached).count(), 1);
+
+    let sent = play(&database, vec![message(14, "/streak")]);
+
+    let expected = "🔥 Current streak: 1 day\n\
+        🏆 Best streak: 1 day\n\
+        🎯 Today: 10/10 answers\n\
+        ❄️ Streak freeze: Off";
+    assert_eq!(sent, vec![Sent::Message(expected.to_string())]);
+}
+
+#[test]
+fn streak_freeze_is_a_setting() {
+    let database = database();
+    play(
+        &database,
+        vec![message(1, "/settings"), choice(2, "settings:streak_freeze")],
+    );
+
+    let sent = play(&database, vec![message(3, "/streak")]);
+
+    let expected = "🔥 Current streak: 0 days\n\
+        🏆 Best streak: 0 days\n\
+        🎯 Today: 0/20 answers\n\
+        ❄️ Streak freeze: On, one missed day a week keeps the streak";
+    assert_eq!(sent, vec![Sent::Message(expected.to_string())]);
+}
+
+#[test]
+fn stop_summarises_the_session() {
+    let nouns: Vec<Noun> = (1..=5)
+        .map(|id| noun(id, "house", &format!("casa{}", id), Gender::Feminine))
+        .collect();
+    let database = InMemoryDatabase::with_nouns(nouns);
+    play(
+        &database,
+        vec![message(1, "/start"), choice(2, "answer:1:masculine")],
+    );
+
+    let sent = play(
+        &database,
+        vec![
+            message(3, "/start"),
+            choice(4, "answer:3:feminine"),
+            choice(5, "answer:4:feminine"),
+            choice(6, "answer:5:masculine"),
+            choice(7, "answer:6:feminine"),
+            message(8, "/stop"),
+        ],
+    );
+
+    let summary = "🏁 Session summary\n\n\
+        Answered: 4\n\
+        Correct: 3 (75%)\n\
+        Best streak: 2 in a row\n\
+        Missed: la casa4";
+    assert_eq!(sent[sent.len() - 2], Sent::Message(summary.to_string()));
+}
+
+#[test]
+fn stop_without_answers_has_no_session_summary() {
+    let database = database();
+    play(&database, vec![message(1, "/start")]);
+
+    let sent = play(&database, vec![message(2, "/stop")]);
+
+    assert_eq!(sent.len(), 1);
+}
+
+#[test]
+fn skip_moves_on_without_grading() {
+    let database = database();
+    play(&database, vec![message(1, "/start")]);
+
+    let sent = play(&database, vec![choice(2, "answer:1:skip")]);
+
+    assert_eq!(sent[0], Sent::Acknowledgement(None));
+    assert_eq!(sent[1], Sent::Edit(2, "Question\n\n⏭ Skipped".to_string()));
+    assert_eq!(sent[2], question(2, "tiempo", "time"));
+    let skipped = database.plays()[0].clone();
+    assert_eq!(skipped.outcome, Some(PlayOutcome::Skipped));
+    assert_eq!(skipped.answer, None);
+    assert!(database.reviews().is_empty());
+}
+
+#[test]
+fn i_dont_know_reveals_the_answer_without_counting_as_wrong() {
+    let database = database();
+    play(&database, vec![message(1, "/start")]);
+
+    let sent = play(&database, vec![choice(2, "answer:1:unknown")]);
+
+    assert_eq!(sent[0], Sent::Acknowledgement(None));
+    assert_eq!(
+        sent[1],
+        Sent::Edit(2, "Question\n\n🤷 I don't know".to_string())
+    );
+    assert!(matches!(&sent[2], Sent::Reply(2, text) if text.starts_with("la casa — feminine")));
+    let unknown = database.plays()[0].clone();
+    assert_eq!(unknown.outcome, Some(PlayOutcome::Unknown));
+    assert_eq!(unknown.answer, None);
+    assert_eq!(database.reviews()[0].repetitions, 0);
+
+    let sent = play(&database, vec![message(3, "/stats")]);
+
+    assert!(matches!(&sent[0], Sent::Message(text)
+        if text.starts_with("You haven't answered any question yet.")));
+}
+
+#[test]
+fn new_users_are_talked_to_in_the_language_of_their_app() {
+    let database = database();
+    let start = ChatEvent::Message(IncomingMessage {
+        chat_id: CHAT_ID,
+        user_id: USER_ID,
+        language_code: Some("uk-UA".to_string()),
+        message_id: 1,
+        text: "/start".to_string(),
+    });
+
+    let sent = play(&database, vec![start]);
+
+    assert!(matches!(&sent[0], Sent::Message(text) if text.starts_with("Вітаємо у грі")));
+    let Sent::Menu(text, rows) = &sent[1] else {
+        panic!("Expected a question, got {:?}", sent[1]);
+    };
+    assert_eq!(text, "Якого роду 'casa' (house)?");
+    assert_eq!(
+        rows[0][0],
+        ("Чоловічий".to_string(), "answer:1:masculine".to_string())
+    );
+    assert_eq!(
+        database.users()[0].settings.ui_language,
+        UiLanguage::Ukrainian
+    );
+}
+
+#[test]
+fn lang_switches_the_interface_language() {
+    let database = database();
+
+    let sent = play(&database, vec![message(1, "/lang")]);
+
+    let Sent::Question(text, choices) = &sent[0] else {
+        panic!("Expected a question, got {:?}", sent[0]);
+    };
+    assert_eq!(text, "🌐 Which language should I talk to you in?");
+    assert!(choices.contains(&("Deutsch".to_string(), "lang:de".to_string())));
+
+    let sent = play(&database, vec![choice(2, "lang:de"), message(3, "/help")]);
+
+    assert_eq!(
+        sent[1],
+        Sent::Edit(2, "🌐 Ab jetzt spreche ich Deutsch mit dir.".to_string())
+    );
+    assert!(matches!(&sent[2], Sent::Message(text) if text.starts_with("/start -> Spiel starten")));
+    assert_eq!(database.users()[0].settings.ui_language, UiLanguage::German);
+}
+
+#[test]
+fn every_catalogue_translates_every_message() {
+    for ui_language in UiLanguage::ALL {
+        assert_eq!(i18n::missing_keys(ui_language), Vec::<&str>::new());
+    }
+}
+
+#[test]
+fn weak_reports_the_genders_endings_and_nouns_gone_wrong() {
+    let database = InMemoryDatabase::with_nouns(vec![
+        noun(1, "house", "casa", Gender::Feminine),
+        noun(2, "table", "mesa", Gender::Feminine),
+    ]);
+    play(&database, vec![message(1, "/start")]);
+    for message_id in 2..14 {
+        let play_id: i32 = database.plays().last().unwrap().id;
+        let data: String = format!("answer:{}:masculine", play_id);
+        play(&database, vec![choice(message_id, &data)]);
+    }
+
+    let sent = play(&database, vec![message(14, "/weak")]);
+
+    let [Sent::Message(text)] = sent.as_slice() else {
+        panic!("Unexpected messages: {:?}", sent);
+    };
+    assert!(text.starts_with("🔍 Where you go wrong in Spanish\n\n"));
+    assert!(text.contains("Genders picked:\n  Feminine: 12 → Masculine\n\n"));
+    // "-asa" and "-esa" only end one noun each, too few to be rated.
+    assert!(text.contains("Hardest endings:\n  -a: 0% (0/12)\n  -sa: 0% (0/12)\n\n"));
+    assert!(text.contains("Hardest nouns:\n  la "));
+}
+
+#[test]
+fn weak_without_answers_invites_to_play() {
+    let database = database();
+
+    let sent = play(&database, vec![message(1, "/weak")]);
+
+    assert_eq!(
+        sent,
+        vec![Sent::Message(
+            "You haven't answered any Spanish noun yet.\nSend /start to play.".to_string()
+        )]
+    );
+}
+
+#[test]
+fn hardest_ranks_the_nouns_everyone_answered_once_refreshed() {
+    let database = InMemoryDatabase::with_nouns(vec![noun(1, "house", "casa", Gender::Feminine)]);
+    play(&database, vec![message(1, "/start")]);
+    for message_id in 2..8 {
+        let play_id: i32 = database.plays().last().unwrap().id;
+        let data: String = format!("answer:{}:masculine", play_id);
+        play(&database, vec![choice(message_id, &data)]);
+    }
+
+    let before_refresh = play_as_admin(&database, vec![USER_ID], vec![message(8, "/hardest")]);
+    database
+        .repositories()
+        .analytics
+        .refresh_noun_difficulty(SystemTime::now())
+        .unwrap();
+    let sent = play_as_admin(&database, vec![USER_ID], vec![message(9, "/hardest 3")]);
+
+    assert_eq!(
+        before_refresh,
+        vec![Sent::Message(
+            "No noun has 5 answers yet. The figures are refreshed every hour.".to_string()
+        )]
+    );
+    let [Sent::Message(text)] = sent.as_slice() else {
+        panic!("Unexpected messages: {:?}", sent);
+    };
+    assert!(text.starts_with(
+        "🧠 Hardest nouns for everyone (at least 5 answers):\n1. la casa (es): 0% (0/6), "
+    ));
+    assert!(text.contains(
+        "\n\n🌱 Easiest nouns for everyone (at least 5 answers):\n1. la casa (es): 0% (0/6), "
+    ));
+}
+
+#[test]
+fn hardest_is_refused_to_non_admins() {
+    let database = database();
+
+    let sent = play(&database, vec![message(1, "/hardest")]);
+
+    assert_eq!(
+        sent,
+        vec![Sent::Message(
+            "This command is only for admins.".to_string()
+        )]
+    );
+}
@@ -0,0 +1,133 @@
+use el_la_la::client::chat::ChatEvent;
+use el_la_la::client::webhook::{WebhookConfig, WebhookServer};
+use std::io::{Read, Write};
+use std::net::{SocketAddr, TcpStream};
+use std::sync::mpsc::{channel, Receiver};
+use std::thread;
+use std::time::Duration;
+
+const PATH: &str = "/telegram/webhook";
+const SECRET_TOKEN: &str = "webhook-secret";
+
+/// Update Telegram posts when a user sends a text message.
+const MESSAGE_UPDATE: &str = r#"{
+    "update_id": 1001,
+    "message": {
+        "message_id": 7,
+        "date": 1700000000,
+        "chat": {"id": 42, "type": "private", "first_name": "Ana"},
+        "from": {"id": 4242, "is_bot": false, "first_name": "Ana", "language_code": "es"},
+        "text": "/start"
+    }
+}"#;
+
+/// Starts a server on a port picked by the system, returning its address and
+/// the events it forwards.
+fn start_server() -> (SocketAddr, Receiver<ChatEvent>) {
+    let config = WebhookConfig {
+        public_url: "https://example.com".to_string(),
+        path: PATH.to_string(),
+        listen_address: "127.0.0.1:0".to_string(),
+        secret_token: SECRET_TOKEN.to_string(),
+    };
+    let server: WebhookServer = WebhookServer::bind(&config).unwrap();
+    let address: SocketAddr = server.local_address().unwrap();
+    let (event_sender, event_receiver) = channel();
+    thread::spawn(move || server.run(event_sender));
+    (address, event_receiver)
+}
+
+/// Sends a request over a plain connection and returns the response status.
+fn request(
+    address: SocketAddr,
+    method: &str,
+    path: &str,
+    secret_token: Option<&str>,
+    body: &str,
+) -> u16 {
+    let mut stream: TcpStream = TcpStream::connect(address).unwrap();
+    let mut head: String = format!(
+        "{} {} HTTP/1.1\r\nHost: {}\r\nContent-Type: application/json\r\n\
+         Content-Length: {}\r\nConnection: close\r\n",
+        method,
+        path,
+        address,
+        body.len()
+    );
+    if let Some(secret_token) = secret_token {
+        head.push_str(&format!(
+            "X-Telegram-Bot-Api-Secret-Token: {}\r\n",
+            secret_token
+        ));
+    }
+    stream
+        .write_all(format!("{}\r\n{}", head, body).as_bytes())
+        .unwrap();
+
+    let mut response: String = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    let status: &str = response.split_whitespace().nth(1).unwrap();
+    status.parse().unwrap()
+}
+
+fn no_event(event_receiver: &Receiver<ChatEvent>) -> bool {
+    event_receiver
+        .recv_timeout(Duration::from_millis(100))
+        .is_err()
+}
+
+#[test]
+fn valid_updates_are_forwarded_as_events() {
+    let (address, event_receiver) = start_server();
+
+    let status: u16 = request(address, "POST", PATH, Some(SECRET_TOKEN), MESSAGE_UPDATE);
+
+    assert_eq!(status, 200);
+    let event: ChatEvent = event_receiver.recv_timeout(Duration::from_secs(5)).unwrap();
+    let ChatEvent::Message(message) = event else {
+        panic!("Unexpected event: {:?}", event);
+    };
+    assert_eq!(message.chat_id, 42);
+    assert_eq!(message.user_id, 4242);
+    assert_eq!(message.message_id, 7);
+    assert_eq!(message.language_code.as_deref(), Some("es"));
+    assert_eq!(message.text, "/start");
+}
+
+#[test]
+fn updates_without_the_secret_token_are_unauthorized() {
+    let (address, event_receiver) = start_server();
+
+    let missing: u16 = request(address, "POST", PATH, None, MESSAGE_UPDATE);
+    let wrong: u16 = request(address, "POST", PATH, Some("guess"), MESSAGE_UPDATE);
+    let prefix: u16 = request(address, "POST", PATH, Some("webhook"), MESSAGE_UPDATE);
+
+    assert_eq!((missing, wrong, prefix), (401, 401, 401));
+    assert!(no_event(&event_receiver));
+}
+
+#[test]
+fn other_paths_are_not_found() {
+    let (address, event_receiver) = start_server();
+
+    let status: u16 = request(
+        address,
+        "POST",
+        "/other/path",
+        Some(SECRET_TOKEN),
+        MESSAGE_UPDATE,
+    );
+
+    assert_eq!(status, 404);
+    assert!(no_event(&event_receiver));
+}
+
+#[test]
+fn other_methods_are_not_allowed() {
+    let (address, event_receiver) = start_server();
+
+    let status: u16 = request(address, "GET", PATH, Some(SECRET_TOKEN), "");
+
+    assert_eq!(status, 405);
+    assert!(no_event(&event_receiver));
+}